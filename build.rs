@@ -0,0 +1,205 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::{env, fs};
+
+/// Generates the `yYYYY` module tree and the solution registry from the `src/yYYYY/dayDD.rs` files,
+/// so new days don't need to be registered by hand in `lib.rs`. Each day module must define a
+/// `DayDD` type implementing `Day`, and each year is only compiled with its `yYYYY` cargo feature.
+fn main() {
+    println!("cargo::rerun-if-changed=src");
+    println!("cargo::rerun-if-changed=Cargo.toml");
+
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let src_dir = manifest_dir.join("src");
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+
+    let years = find_solutions(&src_dir);
+
+    let manifest: toml::Table = fs::read_to_string(manifest_dir.join("Cargo.toml"))
+        .unwrap()
+        .parse()
+        .unwrap();
+    let features = manifest
+        .get("features")
+        .and_then(|features| features.as_table());
+    for year in years.keys() {
+        if !features.is_some_and(|features| features.contains_key(year)) {
+            println!(
+                "cargo::warning=src/{year} is never compiled, add a `{year}` feature to Cargo.toml"
+            );
+        }
+    }
+
+    let mut out = String::new();
+    for (year, days) in &years {
+        writeln!(out, "#[cfg(feature = \"{year}\")]").unwrap();
+        writeln!(out, "pub mod {year} {{").unwrap();
+        for day in days {
+            let day_path = src_dir.join(year).join(day).with_extension("rs");
+            writeln!(out, "    #[path = {day_path:?}]").unwrap();
+            writeln!(out, "    pub mod {day};").unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+        writeln!(out).unwrap();
+        let solvers: Vec<String> = days
+            .iter()
+            .map(|day| format!("{day}: {}", solver_name(day)))
+            .collect();
+        writeln!(out, "#[cfg(feature = \"{year}\")]").unwrap();
+        writeln!(out, "make_solutions!({year} {});", solvers.join(", ")).unwrap();
+        writeln!(out).unwrap();
+    }
+
+    writeln!(
+        out,
+        "fn all_solutions() -> impl Iterator<Item = Solution> {{"
+    )
+    .unwrap();
+    writeln!(out, "    let solutions = std::iter::empty();").unwrap();
+    for year in years.keys() {
+        writeln!(out, "    #[cfg(feature = \"{year}\")]").unwrap();
+        writeln!(out, "    let solutions = solutions.chain({year}());").unwrap();
+    }
+    writeln!(out, "    solutions").unwrap();
+    writeln!(out, "}}").unwrap();
+    writeln!(out).unwrap();
+
+    writeln!(out, "const YEAR_FEATURES: &[&str] = &[").unwrap();
+    for year in years.keys() {
+        writeln!(out, "    #[cfg(feature = \"{year}\")]").unwrap();
+        writeln!(out, "    \"{year}\",").unwrap();
+    }
+    writeln!(out, "];").unwrap();
+    writeln!(out).unwrap();
+
+    write_embedded_inputs(&mut out, &manifest_dir, &years);
+
+    fs::write(out_path, out).unwrap();
+
+    // Lets perf history tell which code a timing came from
+    println!("cargo::rerun-if-changed=.git/HEAD");
+    println!("cargo::rerun-if-changed=.git/logs/HEAD");
+    println!("cargo::rustc-env=AOC_GIT_COMMIT={}", git_commit());
+}
+
+/// The checked out commit, suffixed with `-dirty` if there are uncommitted changes, or `unknown`
+/// outside a git checkout.
+fn git_commit() -> String {
+    let git = |args: &[&str]| {
+        let output = Command::new("git").args(args).output().ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_owned())
+    };
+
+    match (
+        git(&["rev-parse", "HEAD"]),
+        git(&["status", "--porcelain", "--untracked-files=no"]),
+    ) {
+        (Some(commit), Some(status)) if !status.is_empty() => format!("{commit}-dirty"),
+        (Some(commit), _) => commit,
+        (None, _) => "unknown".to_owned(),
+    }
+}
+
+/// Writes `embedded_input`, which returns the inputs that were present at build time when the
+/// `embed-inputs` feature is on. Inputs are looked up where `paths::input` would find them, and
+/// only for the years being compiled.
+fn write_embedded_inputs(
+    out: &mut String,
+    manifest_dir: &Path,
+    years: &BTreeMap<String, Vec<String>>,
+) {
+    let mut arms = vec![];
+
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_some() {
+        println!("cargo::rerun-if-env-changed=AOC_DATA_DIR");
+        let data_dir = match env::var_os("AOC_DATA_DIR") {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => manifest_dir.to_owned(),
+        };
+
+        for (year, days) in years {
+            if env::var_os(format!("CARGO_FEATURE_{}", year.to_uppercase())).is_none() {
+                continue;
+            }
+
+            // Picks up inputs downloaded since the last build
+            let year_dir = data_dir.join("input").join(year);
+            println!("cargo::rerun-if-changed={}", year_dir.display());
+
+            for day in days {
+                let input_path = year_dir.join(day).with_extension("txt");
+                if input_path.is_file() {
+                    let (year, day) = (&year[1..], day[3..].trim_start_matches('0'));
+                    arms.push(format!(
+                        "        ({year}, {day}) => Some(include_str!({input_path:?})),"
+                    ));
+                }
+            }
+        }
+    }
+
+    writeln!(out, "// Unused if no year's feature is enabled").unwrap();
+    writeln!(out, "#[allow(dead_code)]").unwrap();
+    writeln!(
+        out,
+        "fn embedded_input(year: u32, day: u32) -> Option<&'static str> {{"
+    )
+    .unwrap();
+    if arms.is_empty() {
+        writeln!(out, "    let _ = (year, day);").unwrap();
+        writeln!(out, "    None").unwrap();
+    } else {
+        writeln!(out, "    match (year, day) {{").unwrap();
+        for arm in arms {
+            writeln!(out, "{arm}").unwrap();
+        }
+        writeln!(out, "        _ => None,").unwrap();
+        writeln!(out, "    }}").unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+}
+
+/// Maps each `yYYYY` directory to its sorted `dayDD` module names.
+fn find_solutions(src_dir: &Path) -> BTreeMap<String, Vec<String>> {
+    let mut years = BTreeMap::new();
+
+    for year_dir in read_dir(src_dir).filter(|p| p.is_dir()) {
+        let Some(year) = file_stem_matching(&year_dir, "y", 4) else {
+            continue;
+        };
+
+        let mut days: Vec<String> = read_dir(&year_dir)
+            .filter(|p| p.extension().is_some_and(|ext| ext == "rs"))
+            .filter_map(|p| file_stem_matching(&p, "day", 2))
+            .collect();
+
+        if !days.is_empty() {
+            days.sort();
+            years.insert(year, days);
+        }
+    }
+
+    years
+}
+
+/// The type implementing `Day` in a day's module, e.g. `Day01` for `day01`.
+fn solver_name(day: &str) -> String { format!("D{}", &day[1..]) }
+
+fn read_dir(dir: &Path) -> impl Iterator<Item = PathBuf> {
+    fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+}
+
+/// Returns the file stem if it is `prefix` followed by exactly `digits` ASCII digits.
+fn file_stem_matching(path: &Path, prefix: &str, digits: usize) -> Option<String> {
+    let stem = path.file_stem()?.to_str()?;
+    let number = stem.strip_prefix(prefix)?;
+
+    (number.len() == digits && number.bytes().all(|b| b.is_ascii_digit())).then(|| stem.to_owned())
+}
@@ -0,0 +1,131 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{Phase, Probe};
+
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// Wraps the system allocator, counting what passes through it. Only counts once installed with
+/// `#[global_allocator]`, which the binary does with the `count-alloc` feature.
+pub struct Counting;
+
+impl Counting {
+    fn allocated(size: usize) {
+        COUNT.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size, Ordering::Relaxed);
+        let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn freed(size: usize) { LIVE.fetch_sub(size, Ordering::Relaxed); }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            Self::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            Self::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        Self::freed(layout.size());
+    }
+
+    // A realloc counts as a new allocation of the new size, replacing the old one
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            Self::freed(layout.size());
+            Self::allocated(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Allocations made during a stretch of code.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Stats {
+    pub count: usize,
+    pub bytes: usize,
+    /// The most that was live at once, on top of what was already live at the start
+    pub peak: usize,
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} allocs, {} allocated, {} peak",
+            self.count,
+            Bytes(self.bytes),
+            Bytes(self.peak)
+        )
+    }
+}
+
+struct Bytes(usize);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+        let mut size = self.0 as f64;
+        let mut unit = 0;
+        while size >= 1024.0 && unit < UNITS.len() - 1 {
+            size /= 1024.0;
+            unit += 1;
+        }
+
+        if unit == 0 {
+            write!(f, "{} B", self.0)
+        } else {
+            write!(f, "{size:.1} {}", UNITS[unit])
+        }
+    }
+}
+
+/// Records [`Stats`] for each phase of a solver.
+#[derive(Debug, Default)]
+pub struct AllocProbe {
+    pub phases: Vec<(Phase, Stats)>,
+    start: (usize, usize, usize),
+}
+
+impl Probe for AllocProbe {
+    fn enter(&mut self, _: Phase) {
+        let live = LIVE.load(Ordering::Relaxed);
+        PEAK.store(live, Ordering::Relaxed);
+        self.start = (
+            COUNT.load(Ordering::Relaxed),
+            BYTES.load(Ordering::Relaxed),
+            live,
+        );
+    }
+
+    fn exit(&mut self, phase: Phase) {
+        let (count, bytes, live) = self.start;
+        self.phases.push((
+            phase,
+            Stats {
+                count: COUNT.load(Ordering::Relaxed) - count,
+                bytes: BYTES.load(Ordering::Relaxed) - bytes,
+                peak: PEAK.load(Ordering::Relaxed).saturating_sub(live),
+            },
+        ));
+    }
+}
@@ -0,0 +1,226 @@
+use std::convert::Infallible;
+use std::error::Error;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::{fmt, fs};
+
+use crate::paths;
+
+/// A puzzle answer.
+///
+/// Only integer types become an [`Integer`]. Text is kept as written apart from trimming, since
+/// digits with leading zeros are answers too. An integer equals text that spells it the same way,
+/// so `42u8` and `" 42"` are the same answer but `"0042"` isn't.
+///
+/// [`Integer`]: Answer::Integer
+#[derive(Debug, Clone, Eq)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    /// Several lines of text, e.g. letters drawn in `#`s that still need reading off.
+    Grid(String),
+}
+
+#[derive(Debug)]
+pub struct InvalidAnswerError(String);
+
+impl Error for InvalidAnswerError {}
+
+impl fmt::Display for InvalidAnswerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Refusing to submit answer: {}", self.0)
+    }
+}
+
+impl Answer {
+    /// Checks that the answer could plausibly be accepted, i.e. it's a single non-empty word.
+    pub fn validate(&self) -> Result<(), InvalidAnswerError> {
+        match self {
+            Answer::Integer(_) => Ok(()),
+            Answer::Text(s) if s.is_empty() => Err(InvalidAnswerError("it's empty".to_owned())),
+            Answer::Text(s) if s.contains(char::is_whitespace) => {
+                Err(InvalidAnswerError(format!("'{s}' contains whitespace")))
+            }
+            Answer::Text(_) => Ok(()),
+            Answer::Grid(_) => Err(InvalidAnswerError("it spans multiple lines".to_owned())),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Integer(a), Answer::Integer(b)) => a == b,
+            (Answer::Integer(n), Answer::Text(s)) | (Answer::Text(s), Answer::Integer(n)) => {
+                n.to_string() == *s
+            }
+            (Answer::Text(a), Answer::Text(b)) | (Answer::Grid(a), Answer::Grid(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{n}"),
+            Answer::Text(s) | Answer::Grid(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<&str> = s.trim_matches('\n').lines().map(str::trim_end).collect();
+
+        Ok(if lines.len() > 1 {
+            Answer::Grid(lines.join("\n"))
+        } else {
+            Answer::Text(s.trim().to_owned())
+        })
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self { s.as_str().into() }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        let Ok(answer) = s.parse();
+        answer
+    }
+}
+
+macro_rules! answer_from_integer {
+    ($($t:ty)*) => ($(
+        impl From<$t> for Answer {
+            fn from(n: $t) -> Self { Answer::Integer(n.into()) }
+        }
+    )*)
+}
+
+answer_from_integer!(u8 u16 u32 u64 i8 i16 i32 i64 i128);
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self { Answer::Integer(n as i128) }
+}
+
+impl From<isize> for Answer {
+    fn from(n: isize) -> Self { Answer::Integer(n as i128) }
+}
+
+/// Answers known to be correct for a day, used to catch regressions.
+///
+/// Stored in `answer/yYYYY/dayDD.txt` as `part1: ...` and `part2: ...` lines.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AnswerRecord {
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl AnswerRecord {
+    pub fn path(year: u32, day: u32) -> PathBuf { paths::answer(year, day) }
+
+    /// Loads the record for a day, which is empty if nothing has been recorded yet.
+    pub fn load(year: u32, day: u32) -> Result<Self, Box<dyn Error>> {
+        let path = Self::path(year, day);
+        if !fs::exists(&path)? {
+            return Ok(Self::default());
+        }
+
+        Ok(Self::parse(&fs::read_to_string(path)?))
+    }
+
+    pub fn parse(s: &str) -> Self {
+        let mut record = Self::default();
+
+        for line in s.lines() {
+            let Some((key, answer)) = line.split_once(':') else {
+                continue;
+            };
+            let answer = Some(answer.trim())
+                .filter(|a| !a.is_empty())
+                .map(Answer::from);
+
+            match key.trim() {
+                "part1" => record.part1 = answer,
+                "part2" => record.part2 = answer,
+                _ => (),
+            }
+        }
+
+        record
+    }
+
+    pub fn save(&self, year: u32, day: u32) -> Result<(), Box<dyn Error>> {
+        let path = Self::path(year, day);
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, self.to_string())?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for AnswerRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(part1) = &self.part1 {
+            writeln!(f, "part1: {part1}")?;
+        }
+        if let Some(part2) = &self.part2 {
+            writeln!(f, "part2: {part2}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_is_kept_as_written() {
+        assert_eq!(
+            Answer::from(" 0124515891\n"),
+            Answer::Text("0124515891".to_owned())
+        );
+        assert_eq!(Answer::from("0124515891").to_string(), "0124515891");
+        assert_eq!(Answer::from("-42"), Answer::Text("-42".to_owned()));
+        assert_eq!(
+            Answer::from("\n#..#\n####  \n"),
+            Answer::Grid("#..#\n####".to_owned())
+        );
+    }
+
+    #[test]
+    fn integers_equal_text_spelling_them() {
+        assert_eq!(Answer::from(42u8), Answer::from(" 42"));
+        assert_eq!(Answer::from("-42"), Answer::from(-42i64));
+        assert_ne!(Answer::from(42u8), Answer::from("0042"));
+        assert_ne!(Answer::from(124515891u64), Answer::from("0124515891"));
+        assert_ne!(Answer::Grid("42".to_owned()), Answer::from(42u8));
+    }
+
+    #[test]
+    fn validation() {
+        assert!(Answer::from(0u8).validate().is_ok());
+        assert!(Answer::from("0124515891").validate().is_ok());
+        assert!(Answer::from("abcdefgh").validate().is_ok());
+        assert!(Answer::from("  ").validate().is_err());
+        assert!(Answer::from("two words").validate().is_err());
+        assert!(Answer::from("#..#\n####").validate().is_err());
+    }
+
+    #[test]
+    fn records_round_trip() {
+        let record = AnswerRecord {
+            part1: Some(Answer::from("0124515891")),
+            part2: Some(Answer::from(20288u32)),
+        };
+        let parsed = AnswerRecord::parse(&record.to_string());
+
+        assert_eq!(parsed, record);
+        assert_eq!(parsed.part1, Some(Answer::Text("0124515891".to_owned())));
+    }
+}
@@ -0,0 +1,30 @@
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+use md5::{Digest, Md5};
+
+/// On-disk store of HTTP response bodies, keyed by the requested URL.
+pub struct HttpCache {
+    dir: PathBuf,
+}
+
+impl HttpCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self { Self { dir: dir.into() } }
+
+    pub fn get(&self, url: &str) -> Option<String> { fs::read_to_string(self.entry_path(url)).ok() }
+
+    pub fn put(&self, url: &str, body: &str) -> Result<(), Box<dyn Error>> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.entry_path(url), body)?;
+        Ok(())
+    }
+
+    fn entry_path(&self, url: &str) -> PathBuf {
+        let key: String = Md5::digest(url)
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        self.dir.join(key).with_extension("txt")
+    }
+}
@@ -0,0 +1,313 @@
+use std::error::Error;
+use std::time::Duration;
+use std::{env, fmt, thread};
+
+use regex::Regex;
+use reqwest::Url;
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::cookie::Jar;
+use scraper::{Html, Selector};
+
+use crate::cache::HttpCache;
+use crate::puzzle::PuzzlePage;
+use crate::{Answer, Part, paths};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+const MAX_ATTEMPTS: u32 = 5;
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(8);
+
+#[derive(Debug)]
+pub struct SessionTokenError;
+
+impl Error for SessionTokenError {}
+
+impl fmt::Display for SessionTokenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Failed to find environment variable: 'AOC_SESSION_TOKEN'"
+        )
+    }
+}
+
+/// The server redirected instead of answering, which it does when the session cookie has expired.
+#[derive(Debug)]
+pub struct SessionExpiredError(pub String);
+
+impl Error for SessionExpiredError {}
+
+impl fmt::Display for SessionExpiredError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Session expired, redirected to '{}' (refresh 'AOC_SESSION_TOKEN')",
+            self.0
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct ArticleNotFoundError(pub String);
+
+impl Error for ArticleNotFoundError {}
+
+impl fmt::Display for ArticleNotFoundError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Failed to find puzzle text in url: '{}'", self.0)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Hint {
+    TooLow,
+    TooHigh,
+}
+
+/// What the server made of a submitted answer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    Correct,
+    Incorrect(Option<Hint>),
+    /// How much longer to wait before answering again, if the server said
+    TooRecent(Option<Duration>),
+    /// The part was already solved, or isn't unlocked yet
+    WrongLevel,
+}
+
+impl SubmitOutcome {
+    /// Classifies the HTML page returned after posting an answer.
+    pub fn parse(html: &str) -> Result<Self, Box<dyn Error>> {
+        let html = Html::parse_document(html);
+        let selector = Selector::parse("article")?;
+
+        let Some(article) = html.select(&selector).next() else {
+            return Err("Failed to parse AoC response".into());
+        };
+
+        let md = htmd::convert(&article.html())?;
+
+        if md.contains("That's the right answer") {
+            Ok(Self::Correct)
+        } else if md.contains("That's not the right answer") {
+            let hint = if md.contains("too low") {
+                Some(Hint::TooLow)
+            } else if md.contains("too high") {
+                Some(Hint::TooHigh)
+            } else {
+                None
+            };
+            Ok(Self::Incorrect(hint))
+        } else if md.contains("You gave an answer too recently") {
+            Ok(Self::TooRecent(wait_time(&md)?))
+        } else if md.contains("You don't seem to be solving the right level") {
+            Ok(Self::WrongLevel)
+        } else {
+            Err("Failed to parse AoC response".into())
+        }
+    }
+}
+
+/// Reads the wait out of e.g. "You have 4m 51s left to wait".
+fn wait_time(md: &str) -> Result<Option<Duration>, Box<dyn Error>> {
+    let re = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait")?;
+
+    Ok(re.captures(md).map(|caps| {
+        let minutes: u64 = caps.get(1).map_or(0, |m| m.as_str().parse().unwrap());
+        let seconds: u64 = caps[2].parse().unwrap();
+        Duration::from_secs(minutes * 60 + seconds)
+    }))
+}
+
+/// Talks to an AoC server, caching everything it downloads.
+///
+/// The base URL defaults to [`DEFAULT_BASE_URL`], but can point at any server that serves the same
+/// routes, e.g. a local stand-in for testing.
+pub struct AocClient {
+    base_url: Url,
+    http: Client,
+    cache: HttpCache,
+}
+
+impl AocClient {
+    pub fn new(
+        base_url: &str,
+        session_token: &str,
+        cache: HttpCache,
+    ) -> Result<Self, Box<dyn Error>> {
+        // Routes are joined onto the base, so it has to look like a directory
+        let base_url: Url = if base_url.ends_with('/') {
+            base_url.parse()?
+        } else {
+            format!("{base_url}/").parse()?
+        };
+
+        let jar = Jar::default();
+        jar.add_cookie_str(&format!("session={session_token}"), &base_url);
+
+        let http = Client::builder()
+            .user_agent("github.com/cynicalico/aoc cynicalico@pm.me")
+            .cookie_provider(jar.into())
+            .redirect(reqwest::redirect::Policy::none())
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(READ_TIMEOUT)
+            .build()?;
+
+        Ok(Self {
+            base_url,
+            http,
+            cache,
+        })
+    }
+
+    /// Builds a client from `AOC_SESSION_TOKEN`, using `base_url` if given, then `AOC_BASE_URL`,
+    /// then [`DEFAULT_BASE_URL`].
+    pub fn from_env(base_url: Option<&str>) -> Result<Self, Box<dyn Error>> {
+        let Ok(session_token) = env::var("AOC_SESSION_TOKEN") else {
+            return Err(SessionTokenError.into());
+        };
+
+        let base_url = match base_url {
+            Some(base_url) => base_url.to_owned(),
+            None => env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_owned()),
+        };
+
+        let cache = HttpCache::new(paths::http_cache());
+
+        Self::new(&base_url, &session_token, cache)
+    }
+
+    pub fn puzzle_url(&self, year: u32, day: u32) -> Result<Url, Box<dyn Error>> {
+        Ok(self.base_url.join(&format!("{year}/day/{day}"))?)
+    }
+
+    /// Fetches the HTML of the puzzle page. Pages without a puzzle description aren't cached.
+    pub fn puzzle_page(
+        &self,
+        year: u32,
+        day: u32,
+        refresh: bool,
+    ) -> Result<String, Box<dyn Error>> {
+        self.get_cached(self.puzzle_url(year, day)?, refresh, |body| {
+            !PuzzlePage::parse(body).markdown().is_empty()
+        })
+    }
+
+    /// Fetches the puzzle input.
+    pub fn input(&self, year: u32, day: u32, refresh: bool) -> Result<String, Box<dyn Error>> {
+        self.get_cached(
+            self.base_url.join(&format!("{year}/day/{day}/input"))?,
+            refresh,
+            |_| true,
+        )
+    }
+
+    /// Posts `answer` for the given part and classifies the response.
+    ///
+    /// Answers that fail [`Answer::validate`] are rejected without contacting the server.
+    pub fn submit(
+        &self,
+        year: u32,
+        day: u32,
+        part: Part,
+        answer: &Answer,
+    ) -> Result<SubmitOutcome, Box<dyn Error>> {
+        answer.validate()?;
+
+        let submit_url = self.base_url.join(&format!("{year}/day/{day}/answer"))?;
+        let params = [
+            ("level", part.number().to_string()),
+            ("answer", answer.to_string()),
+        ];
+
+        // Anything past a failed connect may have been recorded as a guess, so don't resend those
+        let r = self.send_with_retry(|| self.http.post(submit_url.clone()).form(&params), false)?;
+
+        SubmitOutcome::parse(&check_status(r)?.text()?)
+    }
+
+    /// Fetches `url`, serving it from the local response cache unless `refresh` is set. Responses
+    /// are only cached if `keep` accepts their body.
+    fn get_cached(
+        &self,
+        url: Url,
+        refresh: bool,
+        keep: impl Fn(&str) -> bool,
+    ) -> Result<String, Box<dyn Error>> {
+        if !refresh && let Some(body) = self.cache.get(url.as_str()) {
+            return Ok(body);
+        }
+
+        let r = self.send_with_retry(|| self.http.get(url.clone()), true)?;
+        // Only successful responses are worth keeping, never a redirect to the login page
+        let body = check_status(r)?.text()?;
+        if keep(&body) {
+            self.cache.put(url.as_str(), &body)?;
+        }
+
+        Ok(body)
+    }
+
+    /// Sends the request built by `request`, backing off and retrying when the connection fails.
+    ///
+    /// Timeouts and 5xx responses are only retried if the request is `idempotent`. Once the
+    /// attempts run out, the last response or error is returned as-is.
+    fn send_with_retry(
+        &self,
+        request: impl Fn() -> RequestBuilder,
+        idempotent: bool,
+    ) -> Result<Response, Box<dyn Error>> {
+        let mut backoff = INITIAL_BACKOFF;
+        let mut attempt = 1;
+
+        loop {
+            let result = request().send();
+
+            let reason = match &result {
+                Ok(r) if idempotent && r.status().is_server_error() => {
+                    format!("Server responded with {}", r.status())
+                }
+                Err(err) if err.is_connect() || (idempotent && err.is_timeout()) => {
+                    format!("Request failed ({err})")
+                }
+                _ => return Ok(result?),
+            };
+
+            if attempt == MAX_ATTEMPTS {
+                return Ok(result?);
+            }
+
+            println!("{reason}, retrying in {backoff:?} (attempt {attempt}/{MAX_ATTEMPTS})...");
+            thread::sleep(backoff);
+
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+            attempt += 1;
+        }
+    }
+}
+
+/// Passes through 2xx responses, turning redirects into [`SessionExpiredError`] and anything else
+/// into an error naming the status.
+fn check_status(r: Response) -> Result<Response, Box<dyn Error>> {
+    let status = r.status();
+
+    if status.is_redirection() {
+        let location = r
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|location| location.to_str().ok())
+            .unwrap_or("an unknown location");
+        return Err(SessionExpiredError(location.to_owned()).into());
+    }
+
+    let r = r.error_for_status()?;
+    if !status.is_success() {
+        return Err(format!("Unexpected response status: {status}").into());
+    }
+
+    Ok(r)
+}
@@ -0,0 +1,174 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::params::{Param, Params};
+use crate::{Answer, Answers};
+
+/// A solution to one day's puzzle.
+///
+/// The input is parsed once and shared by both parts. Part 2 is also handed part 1's answer so it
+/// can build on it rather than redo the work; that's `None` if part 1 is unsolved. Parts that
+/// aren't solved yet can be left out and default to `None`.
+///
+/// Values that differ between the examples and the real input are declared in `PARAMS` and read
+/// from `params` during parsing.
+///
+/// Days can also provide a generator of random inputs, `size` elements big (cities, wires, etc.),
+/// for stress testing well beyond the official input, and a slow but obviously correct reference
+/// solution that works straight off the raw input, to cross-check the real one against.
+pub trait Day {
+    type Input<'a>;
+    type Part1: Into<Answer>;
+    type Part2: Into<Answer>;
+
+    const PARAMS: &'static [Param] = &[];
+
+    fn parse<'a>(input: &'a str, params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>>;
+
+    fn part1(_input: &Self::Input<'_>) -> Option<Self::Part1> { None }
+
+    fn part2(_input: &Self::Input<'_>, _part1: Option<&Self::Part1>) -> Option<Self::Part2> { None }
+
+    fn generate(_seed: u64, _size: usize) -> Option<String> { None }
+
+    #[allow(clippy::type_complexity)]
+    fn reference(
+        _input: &str,
+        _params: &Params,
+    ) -> Option<(Option<Self::Part1>, Option<Self::Part2>)> {
+        None
+    }
+}
+
+/// One part of a puzzle, to run or submit on its own.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Part {
+    #[cfg_attr(feature = "cli", value(name = "p1", alias = "1"))]
+    One,
+    #[cfg_attr(feature = "cli", value(name = "p2", alias = "2"))]
+    Two,
+}
+
+impl Part {
+    /// The part's number, which the site calls its level.
+    pub fn number(self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "Parse"),
+            Phase::Part1 => write!(f, "Part 1"),
+            Phase::Part2 => write!(f, "Part 2"),
+        }
+    }
+}
+
+/// Observes a solver moving through its phases, e.g. to measure each one separately.
+pub trait Probe {
+    fn enter(&mut self, phase: Phase);
+
+    fn exit(&mut self, phase: Phase);
+}
+
+impl Probe for () {
+    fn enter(&mut self, _: Phase) {}
+
+    fn exit(&mut self, _: Phase) {}
+}
+
+impl<P: Probe + ?Sized> Probe for &mut P {
+    fn enter(&mut self, phase: Phase) { (**self).enter(phase) }
+
+    fn exit(&mut self, phase: Phase) { (**self).exit(phase) }
+}
+
+/// Lets two probes watch the same run. The second is entered last and exited first, so it
+/// measures as little of the first as possible.
+impl<A: Probe, B: Probe> Probe for (A, B) {
+    fn enter(&mut self, phase: Phase) {
+        self.0.enter(phase);
+        self.1.enter(phase);
+    }
+
+    fn exit(&mut self, phase: Phase) {
+        self.1.exit(phase);
+        self.0.exit(phase);
+    }
+}
+
+/// Type-erased [`Day`], so solutions with different input and answer types can share a registry.
+pub trait Solver {
+    fn params(&self) -> &'static [Param];
+
+    fn solve(&self, input: &str, params: &Params) -> Result<Answers, Box<dyn Error>> {
+        self.solve_probed(input, params, None, &mut ())
+    }
+
+    /// Solves `only` one part if given, otherwise both, while `probe` watches. Part 2 is solved
+    /// alone as it would be if part 1 were unsolved.
+    fn solve_probed(
+        &self,
+        input: &str,
+        params: &Params,
+        only: Option<Part>,
+        probe: &mut dyn Probe,
+    ) -> Result<Answers, Box<dyn Error>>;
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String>;
+
+    fn reference(&self, input: &str, params: &Params) -> Option<Answers>;
+}
+
+impl<D: Day> Solver for D {
+    fn params(&self) -> &'static [Param] { D::PARAMS }
+
+    fn solve_probed(
+        &self,
+        input: &str,
+        params: &Params,
+        only: Option<Part>,
+        probe: &mut dyn Probe,
+    ) -> Result<Answers, Box<dyn Error>> {
+        probe.enter(Phase::Parse);
+        let parsed = D::parse(input, params);
+        probe.exit(Phase::Parse);
+        let parsed = parsed?;
+
+        let mut part1 = None;
+        if only != Some(Part::Two) {
+            probe.enter(Phase::Part1);
+            part1 = D::part1(&parsed);
+            probe.exit(Phase::Part1);
+        }
+
+        let mut part2 = None;
+        if only != Some(Part::One) {
+            probe.enter(Phase::Part2);
+            part2 = D::part2(&parsed, part1.as_ref());
+            probe.exit(Phase::Part2);
+        }
+
+        Ok((part1.map(Into::into), part2.map(Into::into)))
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String> { D::generate(seed, size) }
+
+    fn reference(&self, input: &str, params: &Params) -> Option<Answers> {
+        let (part1, part2) = D::reference(input, params)?;
+        Some((part1.map(Into::into), part2.map(Into::into)))
+    }
+}
@@ -0,0 +1,140 @@
+use std::error::Error;
+use std::ffi::c_void;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::{slice, str};
+
+use libloading::{Library, Symbol};
+
+use crate::{Answer, Answers, Params, find_solution};
+
+/// Receives each result of [`aoc_solve`]: the answer to part `kind`, or an error if `kind` is 0.
+pub type Emit = unsafe extern "C" fn(ctx: *mut c_void, kind: u32, text: *const u8, len: usize);
+
+type SolveFn = unsafe extern "C" fn(
+    year: u32,
+    day: u32,
+    input: *const u8,
+    input_len: usize,
+    params: *const u8,
+    params_len: usize,
+    emit: Emit,
+    ctx: *mut c_void,
+) -> bool;
+
+/// Entry point of the library `run --hot` loads. Solves a day with `params` given as
+/// `name=value` lines, and hands the answers back as text through `emit`, since Rust types can't
+/// safely cross between separately compiled copies of this crate.
+///
+/// # Safety
+///
+/// `input` and `params` must point to valid UTF-8 of the given lengths.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn aoc_solve(
+    year: u32,
+    day: u32,
+    input: *const u8,
+    input_len: usize,
+    params: *const u8,
+    params_len: usize,
+    emit: Emit,
+    ctx: *mut c_void,
+) -> bool {
+    let input = unsafe { str::from_utf8_unchecked(slice::from_raw_parts(input, input_len)) };
+    let params = unsafe { str::from_utf8_unchecked(slice::from_raw_parts(params, params_len)) };
+    let emit = |kind: u32, text: &str| unsafe { emit(ctx, kind, text.as_ptr(), text.len()) };
+
+    // Unwinding across the FFI boundary would abort the runner
+    let result = panic::catch_unwind(AssertUnwindSafe(|| -> Result<Answers, Box<dyn Error>> {
+        let solution =
+            find_solution(year, day).ok_or(format!("No solution found for {year} Day {day:02}"))?;
+        let overrides: Vec<(&str, &str)> =
+            params.lines().filter_map(|l| l.split_once('=')).collect();
+        let params = Params::new(solution.solver.params(), &overrides)?;
+
+        solution.solver.solve(input, &params)
+    }));
+
+    match result {
+        Ok(Ok((part1, part2))) => {
+            for (kind, answer) in [(1, part1), (2, part2)] {
+                if let Some(answer) = answer {
+                    emit(kind, &answer.to_string());
+                }
+            }
+            true
+        }
+        Ok(Err(err)) => {
+            emit(0, &err.to_string());
+            false
+        }
+        Err(_) => {
+            emit(0, "Solution panicked");
+            false
+        }
+    }
+}
+
+/// A loaded build of the solutions library.
+pub struct HotLibrary {
+    library: Library,
+}
+
+impl HotLibrary {
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let library = unsafe { Library::new(path)? };
+        Ok(Self { library })
+    }
+
+    pub fn solve(
+        &self,
+        year: u32,
+        day: u32,
+        input: &str,
+        overrides: &[(String, String)],
+    ) -> Result<Answers, Box<dyn Error>> {
+        #[derive(Default)]
+        struct Results {
+            answers: [Option<String>; 2],
+            error: Option<String>,
+        }
+
+        unsafe extern "C" fn collect(ctx: *mut c_void, kind: u32, text: *const u8, len: usize) {
+            let results = unsafe { &mut *(ctx as *mut Results) };
+            let text = unsafe { String::from_utf8_lossy(slice::from_raw_parts(text, len)) };
+            match kind {
+                1 | 2 => results.answers[kind as usize - 1] = Some(text.into_owned()),
+                _ => results.error = Some(text.into_owned()),
+            }
+        }
+
+        let params: String = overrides
+            .iter()
+            .map(|(name, value)| format!("{name}={value}\n"))
+            .collect();
+
+        let mut results = Results::default();
+        unsafe {
+            let solve: Symbol<SolveFn> = self.library.get(b"aoc_solve")?;
+            solve(
+                year,
+                day,
+                input.as_ptr(),
+                input.len(),
+                params.as_ptr(),
+                params.len(),
+                collect,
+                &mut results as *mut Results as *mut c_void,
+            );
+        }
+
+        if let Some(error) = results.error {
+            return Err(error.into());
+        }
+
+        let [part1, part2] = results
+            .answers
+            .map(|answer| answer.map(|a| a.parse::<Answer>().unwrap()));
+        Ok((part1, part2))
+    }
+}
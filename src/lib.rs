@@ -1,73 +1,208 @@
-#![feature(let_chains)]
-
+use std::borrow::Cow;
 use std::error::Error;
-use std::iter::empty;
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
 use std::path::{Path, PathBuf};
-use std::{fmt, fs};
-
-use crate::util::parse::ParseOps;
+use std::{fmt, fs, io};
+
+pub use crate::answers::Answer;
+pub use crate::day::{Day, Part, Phase, Probe, Solver};
+pub use crate::params::{Param, Params};
+use crate::runner::TimingProbe;
+pub use crate::runner::{Mismatch, Report, Timings};
+pub use crate::selection::Selection;
+
+pub mod alloc;
+pub mod answers;
+pub mod cache;
+#[cfg(feature = "cli")]
+pub mod client;
+pub mod day;
+#[cfg(feature = "hot-reload")]
+pub mod hot;
+pub mod markdown;
+pub mod params;
+pub mod paths;
+pub mod perf;
+#[cfg(feature = "cli")]
+pub mod puzzle;
+pub mod runner;
+pub mod selection;
+pub mod term;
 
 pub mod util {
     pub mod array;
     pub mod id_cache;
     pub mod integer;
+    pub mod ocr;
     pub mod parse;
+    pub mod rng;
+    pub mod viz;
 }
 
-pub mod y2015 {
-    pub mod day01;
-    pub mod day02;
-    pub mod day03;
-    pub mod day04;
-    pub mod day05;
-    pub mod day06;
-    pub mod day07;
-    pub mod day08;
-    pub mod day09;
-    pub mod day10;
-}
+pub type Answers = (Option<Answer>, Option<Answer>);
 
 pub struct Solution {
     pub year: u32,
     pub day: u32,
     pub input_path: PathBuf,
-    pub wrapper: fn(&str) -> Result<(Option<String>, Option<String>), Box<dyn Error>>,
+    /// The input built into the binary by the `embed-inputs` feature, used instead of `input_path`
+    pub embedded_input: Option<&'static str>,
+    pub solver: &'static dyn Solver,
 }
 
-macro_rules! make_solutions {
-    ($year:tt $($day:tt),*) => {
-        pub fn $year() -> Vec<Solution> {
-            vec![$({
-                let year = stringify!($year);
-                let day = stringify!($day);
+impl Solution {
+    /// The puzzle input, embedded or read from `input_path`.
+    pub fn input(&self) -> io::Result<Cow<'static, str>> {
+        match self.embedded_input {
+            Some(input) => Ok(Cow::Borrowed(input)),
+            None => fs::read_to_string(&self.input_path).map(Cow::Owned),
+        }
+    }
+
+    /// Whether there's an input to solve, embedded or on disk.
+    pub fn has_input(&self) -> bool { self.embedded_input.is_some() || self.input_path.exists() }
 
-                let input_path = Path::new("input").join(year).join(day).with_extension("txt");
+    /// Solves both parts of `input`, timing each phase.
+    pub fn solve(&self, input: &str, params: &Params) -> Result<Report, Box<dyn Error>> {
+        self.solve_probed(input, params, None, &mut ())
+    }
 
-                let wrapper = |filepath: &str| {
-                    use $year::$day::*;
+    /// Like [`Solution::solve`], but only solves `only` one part if given, and lets `probe`
+    /// observe each phase of the solver.
+    pub fn solve_probed(
+        &self,
+        input: &str,
+        params: &Params,
+        only: Option<Part>,
+        probe: &mut dyn Probe,
+    ) -> Result<Report, Box<dyn Error>> {
+        let mut probes = (probe, TimingProbe::default());
+        let (part1, part2) = self.solver.solve_probed(input, params, only, &mut probes)?;
+
+        Ok(Report {
+            year: self.year,
+            day: self.day,
+            part1,
+            part2,
+            timings: probes.1.timings,
+        })
+    }
 
-                    let input = fs::read_to_string(filepath)?;
-                    let parsed = parse(&input)?;
+    /// Reads the input at `path` and solves both parts.
+    pub fn run(&self, path: &Path, params: &Params) -> Result<Report, Box<dyn Error>> {
+        self.run_probed(path, params, None, &mut ())
+    }
 
-                    Ok((part1(&parsed).map(|v| v.to_string()), part2(&parsed).map(|v| v.to_string())))
-                };
+    /// Like [`Solution::run`], with the options of [`Solution::solve_probed`].
+    pub fn run_probed(
+        &self,
+        path: &Path,
+        params: &Params,
+        only: Option<Part>,
+        probe: &mut dyn Probe,
+    ) -> Result<Report, Box<dyn Error>> {
+        let input = fs::read_to_string(path)?;
+        self.solve_probed(&input, params, only, probe)
+    }
 
-                Solution { year: year.unsigned(), day: day.unsigned(), input_path, wrapper }
+    /// Solves an input generated from each of `seeds` and compares the answers with the reference
+    /// solution's, returning the inputs they disagreed on. Panics count as disagreeing.
+    ///
+    /// Fails if the day has no generator, or the reference solution can't answer an input.
+    pub fn cross_check(
+        &self,
+        size: usize,
+        seeds: Range<u64>,
+    ) -> Result<Vec<Mismatch>, Box<dyn Error>> {
+        let (year, day) = (self.year, self.day);
+        let params = Params::defaults(self.solver.params());
+
+        let mut mismatches = vec![];
+        for seed in seeds {
+            let Some(input) = self.solver.generate(seed, size) else {
+                return Err(format!("No input generator for {year} Day {day:02}").into());
+            };
+            let Some(expected) = self.solver.reference(&input, &params) else {
+                return Err(
+                    format!("No reference answers for {year} Day {day:02} (seed {seed})").into(),
+                );
+            };
+
+            let actual = panic::catch_unwind(AssertUnwindSafe(|| {
+                self.solver
+                    .solve(&input, &params)
+                    .map_err(|err| err.to_string())
+            }))
+            .unwrap_or_else(|_| Err("panicked".to_owned()));
+
+            if actual.as_ref() != Ok(&expected) {
+                mismatches.push(Mismatch {
+                    seed,
+                    expected,
+                    actual,
+                });
+            }
+        }
+
+        Ok(mismatches)
+    }
+}
+
+// Unused if no year's feature is enabled
+#[allow(unused_macros)]
+macro_rules! make_solutions {
+    ($year:tt $($day:tt: $solver:tt),*) => {
+        pub fn $year() -> Vec<Solution> {
+            use $crate::util::parse::ParseOps;
+
+            vec![$({
+                let year = stringify!($year).unsigned();
+                let day = stringify!($day).unsigned();
+
+                Solution {
+                    year,
+                    day,
+                    input_path: $crate::paths::input(year, day),
+                    embedded_input: embedded_input(year, day),
+                    solver: &$year::$day::$solver,
+                }
             },)*]
         }
     }
 }
 
-make_solutions!(y2015
-    day01, day02, day03, day04, day05,
-    day06, day07, day08, day09, day10
-);
+// Declares a `yYYYY` module and `make_solutions!` call for every `src/yYYYY/dayDD.rs`, an
+// `all_solutions` that chains them together, the enabled `YEAR_FEATURES`, and the
+// `embedded_input` lookup. See `build.rs`. `cargo fmt` can't follow these modules, so the day files
+// are formatted separately (see the README).
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+/// The cargo features this build was compiled with, so it can be rebuilt the same way.
+pub fn features() -> impl Iterator<Item = &'static str> {
+    let others = [
+        cfg!(feature = "cli").then_some("cli"),
+        cfg!(feature = "count-alloc").then_some("count-alloc"),
+        cfg!(feature = "hot-reload").then_some("hot-reload"),
+        cfg!(feature = "embed-inputs").then_some("embed-inputs"),
+    ];
+
+    YEAR_FEATURES
+        .iter()
+        .copied()
+        .chain(others.into_iter().flatten())
+}
+
+/// Looks up the solution to one day's puzzle.
+pub fn find_solution(year: u32, day: u32) -> Option<Solution> {
+    all_solutions().find(|solution| solution.year == year && solution.day == day)
+}
 
-pub fn filtered_solutions(year: Option<u32>, day: Option<u32>) -> Vec<Solution> {
-    empty()
-        .chain(y2015())
-        .filter(|s| year.is_none_or(|y| y == s.year))
-        .filter(|s| day.is_none_or(|y| y == s.day))
+/// The solutions for the selected years and days, or all of them if there's no selection.
+pub fn filtered_solutions(year: Option<&Selection>, day: Option<&Selection>) -> Vec<Solution> {
+    all_solutions()
+        .filter(|s| year.is_none_or(|y| y.contains(s.year)))
+        .filter(|s| day.is_none_or(|d| d.contains(s.day)))
         .collect()
 }
 
@@ -1,19 +1,34 @@
-use std::collections::HashMap;
+use std::borrow::Cow;
 use std::error::Error;
+use std::panic::{self, AssertUnwindSafe};
 use std::path::{Path, PathBuf};
-use std::time::{Duration, Instant};
-use std::{env, fmt, fs};
-
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{env, fs, process, thread};
+
+use aoc::alloc::AllocProbe;
+use aoc::answers::AnswerRecord;
+use aoc::client::{AocClient, ArticleNotFoundError, Hint, SubmitOutcome};
+use aoc::params::{self, ParamError};
+use aoc::perf::{self, PerfLog};
+use aoc::puzzle::PuzzlePage;
+use aoc::term::{self, Style};
+use aoc::util::viz;
 use aoc::*;
-use clap::{Parser, Subcommand, ValueEnum};
-use reqwest::Url;
-use reqwest::cookie::Jar;
-use scraper::{Html, Selector};
+use clap::{Parser, Subcommand};
+use itertools::Itertools;
+
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOC: aoc::alloc::Counting = aoc::alloc::Counting;
 
 /// AoC CLI
 #[derive(Debug, Parser)]
 #[clap(name = "aoc-cli", version)]
 pub struct App {
+    /// Server to talk to instead of adventofcode.com (defaults to $AOC_BASE_URL if set)
+    #[arg(long, global = true, required = false)]
+    base_url: Option<String>,
+
     #[clap(subcommand)]
     command: Command,
 }
@@ -27,25 +42,134 @@ enum Command {
 
         /// Day to download
         day: u32,
+
+        /// Refetch from the server instead of using cached responses
+        #[arg(short, long, required = false)]
+        refresh: bool,
+    },
+
+    /// Read the puzzle description, downloading it if needed
+    Read {
+        /// Year to read
+        year: u32,
+
+        /// Day to read
+        day: u32,
     },
 
     /// Run solutions
     Run {
-        /// Year to run
+        /// Years to run, e.g. `2015`, `2015,2017` or `2015..=2017`
         #[arg(short, long, required = false)]
-        year: Option<u32>,
+        year: Option<Selection>,
 
-        /// Day to run
+        /// Days to run, e.g. `3`, `3,7,9`, `1..=5` or `1..6`
         #[arg(short, long, required = false)]
-        day: Option<u32>,
+        day: Option<Selection>,
+
+        /// Only run this part, skipping the other entirely
+        #[arg(long)]
+        part: Option<Part>,
 
         /// Input file to use instead of default
         #[arg(short, long, required = false, requires = "year", requires = "day")]
         input_path_override: Option<PathBuf>,
 
+        /// Override a parameter the solution declares, e.g. `--param iterations=5`
+        #[arg(
+            short,
+            long = "param",
+            value_name = "NAME=VALUE",
+            value_parser = params::parse_override,
+            requires = "year",
+            requires = "day"
+        )]
+        param: Vec<(String, String)>,
+
+        /// Write frames from solutions that draw their progress to this directory, or animate
+        /// them in the terminal if it's `-`
+        #[arg(long, value_name = "DIR")]
+        visualize: Option<PathBuf>,
+
+        /// Report allocations made while parsing and solving each part (needs the `count-alloc`
+        /// feature)
+        #[arg(long)]
+        alloc: bool,
+
         /// Print totals
         #[arg(short, long, required = false)]
         totals: bool,
+
+        /// Rebuild and rerun whenever the day's source or input changes
+        #[arg(short, long, requires = "year", requires = "day")]
+        watch: bool,
+
+        /// Like `--watch`, but only rebuild the solutions library and reload it into this process
+        /// (needs the `hot-reload` feature)
+        #[arg(long, requires = "year", requires = "day", conflicts_with = "watch")]
+        hot: bool,
+    },
+
+    /// List solutions and which of their files exist locally
+    List {
+        /// Years to list
+        #[arg(short, long, required = false)]
+        year: Option<Selection>,
+
+        /// Days to list
+        #[arg(short, long, required = false)]
+        day: Option<Selection>,
+
+        /// Also solve each input, to show which parts the solution answers
+        #[arg(long)]
+        check: bool,
+    },
+
+    /// Generate a random input for stress testing
+    Gen {
+        /// Year to generate for
+        year: u32,
+
+        /// Day to generate for
+        day: u32,
+
+        /// How big to make it, in whatever the day counts (cities, wires, instructions, etc.)
+        size: usize,
+
+        /// Seed for a reproducible input, otherwise one is picked and printed
+        #[arg(short, long)]
+        seed: Option<u64>,
+
+        /// Where to write the input, instead of input/yYYYY/dayDD.gen-SEED-SIZE.txt
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Check a solution against its reference solution on generated inputs
+    CrossCheck {
+        /// Year to check
+        year: u32,
+
+        /// Day to check
+        day: u32,
+
+        /// Size of each generated input
+        #[arg(long, default_value_t = 20)]
+        size: usize,
+
+        /// Number of inputs to try
+        #[arg(short, long, default_value_t = 100)]
+        runs: u64,
+
+        /// Seed of the first input, later ones count up from it
+        #[arg(short, long, default_value_t = 0)]
+        seed: u64,
+    },
+
+    /// Look at timings recorded by previous runs
+    Perf {
+        #[clap(subcommand)]
+        command: PerfCommand,
     },
 
     /// Submit puzzle answer
@@ -57,105 +181,150 @@ enum Command {
         day: u32,
 
         /// Part of the puzzle
-        part: PuzzlePart,
+        part: Part,
     },
 }
 
-#[derive(Clone, Debug, ValueEnum)]
-enum PuzzlePart {
-    P1,
-    P2,
+#[derive(Debug, Subcommand)]
+enum PerfCommand {
+    /// Compare this build's timings with another commit's
+    Compare {
+        /// Commit to compare against, defaults to the last other one with timings on this machine
+        rev: Option<String>,
+
+        /// Slowdown, in percent, above which a day counts as a regression
+        #[arg(short, long, default_value_t = 10.0)]
+        threshold: f64,
+    },
 }
 
+/// Set on the runs `run --watch` starts, so they don't start watching too.
+const WATCH_CHILD_VAR: &str = "AOC_WATCH_CHILD";
+
+/// How often `run --watch` checks for changes.
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Days that take longer than this are highlighted by `run`.
+const SLOW: Duration = Duration::from_secs(1);
+
+/// Smallest slowdown `perf compare` flags, however big it is relatively.
+const MIN_REGRESSION: Duration = Duration::from_millis(1);
+
+const DEFAULT_SOLUTION_TEMPLATE: &str = include_str!("../templates/solution.rs");
+
 fn main() {
-    let args = App::parse();
+    let mut args = App::parse();
+
+    // Rerun by `run --watch`, which does the watching itself
+    if env::var_os(WATCH_CHILD_VAR).is_some()
+        && let Command::Run { watch, .. } = &mut args.command
+    {
+        *watch = false;
+    }
 
     if let Err(err) = match args.command {
-        Command::Download { year, day } => download(year, day),
+        Command::Download { year, day, refresh } => {
+            download(args.base_url.as_deref(), year, day, refresh)
+        }
+        Command::Read { year, day } => read(args.base_url.as_deref(), year, day),
         Command::Run {
             year,
             day,
             input_path_override,
+            param,
+            visualize,
+            part,
+            alloc,
+            totals,
+            watch: false,
+            hot: false,
+        } => run(
+            RunFilter { year, day, part },
+            input_path_override,
+            &param,
+            visualize,
+            alloc,
             totals,
-        } => run(year, day, input_path_override, totals),
-        Command::Submit { year, day, part } => submit(year, day, part),
+        ),
+        Command::Run {
+            year,
+            day,
+            input_path_override,
+            watch: true,
+            ..
+        } => watch(year, day, input_path_override),
+        Command::Run {
+            year,
+            day,
+            input_path_override,
+            param,
+            hot: true,
+            ..
+        } => hot_run(year, day, input_path_override, &param),
+        Command::List { year, day, check } => list(year, day, check),
+        Command::Gen {
+            year,
+            day,
+            size,
+            seed,
+            output,
+        } => generate(year, day, size, seed, output),
+        Command::CrossCheck {
+            year,
+            day,
+            size,
+            runs,
+            seed,
+        } => cross_check(year, day, size, runs, seed),
+        Command::Perf {
+            command: PerfCommand::Compare { rev, threshold },
+        } => perf_compare(rev, threshold),
+        Command::Submit { year, day, part } => submit(args.base_url.as_deref(), year, day, part),
     } {
         println!("Error: {err}");
+        process::exit(1);
     }
 }
 
-#[derive(Debug)]
-struct SessionTokenError;
-
-impl Error for SessionTokenError {}
-
-impl fmt::Display for SessionTokenError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "Failed to find environment variable: 'AOC_SESSION_TOKEN'"
-        )
-    }
-}
-
-#[derive(Debug)]
-struct ArticleNotFoundError(String);
-
-impl Error for ArticleNotFoundError {}
-
-impl fmt::Display for ArticleNotFoundError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Failed to find puzzle text in url: '{}'", self.0)
-    }
-}
-
-fn download(year: u32, day: u32) -> Result<(), Box<dyn Error>> {
-    let client = http_client()?;
+fn download(
+    base_url: Option<&str>,
+    year: u32,
+    day: u32,
+    refresh: bool,
+) -> Result<(), Box<dyn Error>> {
+    let client = AocClient::from_env(base_url)?;
 
-    download_puzzle(&client, year, day, false)?;
-    download_input(&client, year, day, false)?;
-    make_solution_template(year, day, false)?;
+    download_puzzle(&client, year, day, refresh)?;
+    download_input(&client, year, day, refresh)?;
+    make_solution_template(&client, year, day, false)?;
 
     Ok(())
 }
 
 fn download_puzzle(
-    client: &reqwest::blocking::Client,
+    client: &AocClient,
     year: u32,
     day: u32,
-    force: bool,
+    refresh: bool,
 ) -> Result<(), Box<dyn Error>> {
-    let puzzle_path = Path::new("puzzle")
-        .join(format!("y{year}"))
-        .join(format!("day{day:02}"))
-        .with_extension("md");
-
-    if force || !fs::exists(&puzzle_path)? {
-        let puzzle_url = format!("https://adventofcode.com/{year}/day/{day}");
+    let puzzle_path = paths::puzzle(year, day);
 
-        let r = client.get(&puzzle_url).send()?;
-
-        let html = Html::parse_document(&r.text()?);
-        let selector = Selector::parse("article.day-desc")?;
-
-        let file_content = html.select(&selector).fold(String::new(), |acc, article| {
-            if let Ok(md) = htmd::convert(&article.html()) {
-                if acc.is_empty() {
-                    acc + &md
-                } else {
-                    acc + &format!("\n\n{md}")
-                }
-            } else {
-                acc
-            }
-        });
+    if refresh || !fs::exists(&puzzle_path)? {
+        let page = PuzzlePage::parse(&client.puzzle_page(year, day, refresh)?);
 
+        let file_content = page.markdown().join("\n\n");
         if file_content.is_empty() {
-            return Err(ArticleNotFoundError(puzzle_url).into());
+            return Err(ArticleNotFoundError(client.puzzle_url(year, day)?.to_string()).into());
         }
 
         fs::create_dir_all(puzzle_path.parent().unwrap())?;
         fs::write(&puzzle_path, file_content)?;
+
+        if let Some(example) = page.example() {
+            fs::write(paths::example(year, day), example)?;
+        }
+
+        record_answers(year, day, &page)?;
     } else {
         println!("{} exists, skipping...", puzzle_path.display());
     }
@@ -163,24 +332,44 @@ fn download_puzzle(
     Ok(())
 }
 
+/// Saves the answers shown on a solved puzzle page, however the puzzle was solved.
+fn record_answers(year: u32, day: u32, page: &PuzzlePage) -> Result<(), Box<dyn Error>> {
+    let mut answers = page.answers().into_iter();
+
+    let mut record = AnswerRecord::load(year, day)?;
+    let before = record.clone();
+
+    if let Some(part1) = answers.next() {
+        record.part1 = Some(part1.into());
+    }
+    if let Some(part2) = answers.next() {
+        record.part2 = Some(part2.into());
+    }
+
+    if record != before {
+        record.save(year, day)?;
+        println!(
+            "Recorded answers in {}",
+            AnswerRecord::path(year, day).display()
+        );
+    }
+
+    Ok(())
+}
+
 fn download_input(
-    client: &reqwest::blocking::Client,
+    client: &AocClient,
     year: u32,
     day: u32,
-    force: bool,
+    refresh: bool,
 ) -> Result<(), Box<dyn Error>> {
-    let input_path = Path::new("input")
-        .join(format!("y{year}"))
-        .join(format!("day{day:02}"))
-        .with_extension("txt");
-
-    if force || !fs::exists(&input_path)? {
-        let input_url = format!("https://adventofcode.com/{year}/day/{day}/input");
+    let input_path = paths::input(year, day);
 
-        let r = client.get(&input_url).send()?;
+    if refresh || !fs::exists(&input_path)? {
+        let input = client.input(year, day, refresh)?;
 
         fs::create_dir_all(input_path.parent().unwrap())?;
-        fs::write(input_path, r.text()?)?;
+        fs::write(input_path, input)?;
     } else {
         println!("{} exists, skipping...", input_path.display());
     }
@@ -188,75 +377,208 @@ fn download_input(
     Ok(())
 }
 
-fn make_solution_template(year: u32, day: u32, force: bool) -> Result<(), Box<dyn Error>> {
-    let solution_path = Path::new("src")
-        .join(format!("y{year}"))
-        .join(format!("day{day:02}"))
-        .with_extension("rs");
+fn make_solution_template(
+    client: &AocClient,
+    year: u32,
+    day: u32,
+    force: bool,
+) -> Result<(), Box<dyn Error>> {
+    let solution_path = paths::source(year, day);
 
     if force || !fs::exists(&solution_path)? {
+        let template =
+            fs::read_to_string(paths::project_root().join("templates").join("solution.rs"))
+                .unwrap_or_else(|_| DEFAULT_SOLUTION_TEMPLATE.to_owned());
+        let page = PuzzlePage::parse(&client.puzzle_page(year, day, false)?);
+
         fs::create_dir_all(solution_path.parent().unwrap())?;
-        fs::write(
-            solution_path,
-            "\
-use std::error::Error;
+        fs::write(solution_path, render_template(&template, year, day, &page))?;
+    } else {
+        println!("{} exists, skipping...", solution_path.display());
+    }
 
-pub type ParsedInput = _;
+    Ok(())
+}
+
+/// Fills in a solution template. Supported placeholders are `{{year}}`, `{{day}}`, `{{day:02}}`,
+/// `{{title}}`, and `{{example}}`, which becomes a raw string literal holding the example input.
+fn render_template(template: &str, year: u32, day: u32, page: &PuzzlePage) -> String {
+    let title = page.title().unwrap_or_default();
+    let example = page.example().unwrap_or_default();
+
+    template
+        .replace("{{year}}", &year.to_string())
+        .replace("{{day}}", &day.to_string())
+        .replace("{{day:02}}", &format!("{day:02}"))
+        .replace("{{title}}", &title)
+        .replace("{{example}}", &raw_string_literal(&example))
+}
 
-pub fn parse(input: &str) -> Result<ParsedInput, Box<dyn Error>> { todo!() }
+/// Quotes `s` as a raw string literal, with enough `#`s that nothing inside can close it.
+fn raw_string_literal(s: &str) -> String {
+    let mut hashes = String::new();
+    while s.contains(&format!("\"{hashes}")) {
+        hashes.push('#');
+    }
+    format!("r{hashes}\"{s}\"{hashes}")
+}
 
-pub fn part1(input: &ParsedInput) -> Option<_> { None }
+fn read(base_url: Option<&str>, year: u32, day: u32) -> Result<(), Box<dyn Error>> {
+    let puzzle_path = paths::puzzle(year, day);
 
-pub fn part2(input: &ParsedInput) -> Option<_> { None }
-",
-        )?;
-    } else {
-        println!("{} exists, skipping...", solution_path.display());
+    if !fs::exists(&puzzle_path)? {
+        let client = AocClient::from_env(base_url)?;
+        download_puzzle(&client, year, day, false)?;
     }
 
+    let md = fs::read_to_string(&puzzle_path)?;
+    print!(
+        "{}",
+        markdown::render(&md, term::width(), term::color_enabled())
+    );
+
     Ok(())
 }
 
+/// Which solutions and parts `run` runs.
+struct RunFilter {
+    year: Option<Selection>,
+    day: Option<Selection>,
+    part: Option<Part>,
+}
+
+/// The one year and day selected, for modes that only work on a single day.
+fn single_day(
+    year: Option<Selection>,
+    day: Option<Selection>,
+) -> Result<(u32, u32), Box<dyn Error>> {
+    match (year.and_then(|y| y.single()), day.and_then(|d| d.single())) {
+        (Some(year), Some(day)) => Ok((year, day)),
+        _ => Err("Pick a single year and day".into()),
+    }
+}
+
 fn run(
-    year: Option<u32>,
-    day: Option<u32>,
+    filter: RunFilter,
     input_path_override: Option<PathBuf>,
+    param_overrides: &[(String, String)],
+    visualize: Option<PathBuf>,
+    alloc: bool,
     totals: bool,
 ) -> Result<(), Box<dyn Error>> {
-    let solutions = filtered_solutions(year, day);
+    if alloc && !cfg!(feature = "count-alloc") {
+        return Err("Counting allocations needs a build with `--features count-alloc`".into());
+    }
+
+    let solutions = filtered_solutions(filter.year.as_ref(), filter.day.as_ref());
+    if solutions.is_empty() {
+        return Err(
+            "No solutions found (years are only compiled with their feature, e.g. y2015)".into(),
+        );
+    }
+    if solutions.len() > 1 && (input_path_override.is_some() || !param_overrides.is_empty()) {
+        return Err("An input file or parameters can only be given for a single day".into());
+    }
+    let param_overrides: Vec<(&str, &str)> = param_overrides
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+        .collect();
 
     let mut solved = 0;
     let mut duration = Duration::ZERO;
+    let perf_log = PerfLog::default();
+    let color = term::color_enabled();
+
+    for solution in solutions {
+        let Solution {
+            year,
+            day,
+            ref input_path,
+            ..
+        } = solution;
 
-    for Solution {
-        year,
-        day,
-        input_path,
-        wrapper,
-    } in solutions
-    {
         println!("{year} Day {day:02}");
 
-        let filepath = &input_path_override.as_ref().unwrap_or(&input_path);
+        let filepath = input_path_override.as_ref().unwrap_or(input_path);
+        let input = match &input_path_override {
+            Some(path) => fs::read_to_string(path).map(Cow::Owned),
+            None => solution.input(),
+        };
+        let params = Params::new(solution.solver.params(), &param_overrides)?;
 
-        let instant = Instant::now();
-        match wrapper(filepath.to_str().unwrap()) {
-            Ok((part1, part2)) => {
-                let elapsed = instant.elapsed();
+        let viz_target = visualize.as_ref().map(|dir| match dir.to_str() {
+            Some("-") => viz::Target::Terminal,
+            _ => viz::Target::Dir(dir.join(format!("y{year}")).join(format!("day{day:02}"))),
+        });
+        viz::set_target(viz_target.clone());
+
+        let mut alloc_probe = AllocProbe::default();
+        let probe: &mut dyn Probe = if alloc { &mut alloc_probe } else { &mut () };
+
+        let result = input
+            .map_err(Box::from)
+            .and_then(|input| solution.solve_probed(&input, &params, filter.part, probe));
+
+        match result {
+            Ok(Report {
+                part1,
+                part2,
+                timings,
+                ..
+            }) => {
+                let elapsed = timings.total();
 
                 solved += if part1.is_some() { 1 } else { 0 };
                 solved += if part2.is_some() { 1 } else { 0 };
                 duration += elapsed;
 
-                println!("  Part 1: {}", part1.unwrap_or("unsolved".to_owned()));
-                println!("  Part 2: {}", part2.unwrap_or("unsolved".to_owned()));
-                println!("  Elapsed: {:.03} s", elapsed.as_nanos() as f64 / 1e9);
+                // Recorded answers only apply to the real input with the default parameters
+                let real_input = input_path_override.is_none() && param_overrides.is_empty();
+                let record = if real_input {
+                    AnswerRecord::load(year, day).unwrap_or_default()
+                } else {
+                    AnswerRecord::default()
+                };
+
+                if filter.part != Some(Part::Two) {
+                    println!("  Part 1: {}", check_answer(part1, record.part1, color));
+                }
+                if filter.part != Some(Part::One) {
+                    println!("  Part 2: {}", check_answer(part2, record.part2, color));
+                }
+                println!("  Elapsed: {}", format_elapsed(elapsed, color));
+
+                // Only full, undisturbed runs on the real input are worth comparing over time
+                if real_input
+                    && filter.part.is_none()
+                    && !alloc
+                    && viz_target.is_none()
+                    && let Err(err) = perf_log.append(&perf::Entry::now(year, day, elapsed))
+                {
+                    println!("  Failed to record timing: {err}");
+                }
+
+                if alloc {
+                    println!("  Allocations:");
+                    for (phase, stats) in &alloc_probe.phases {
+                        println!("    {phase}: {stats}");
+                    }
+                }
+
+                match (&viz_target, viz::frame_count()) {
+                    (None, _) => (),
+                    (Some(_), 0) => println!("  No frames (this solution doesn't visualise)"),
+                    (Some(viz::Target::Dir(dir)), n) => {
+                        println!("  Frames: {n} in {}", dir.display())
+                    }
+                    (Some(viz::Target::Terminal), n) => println!("  Frames: {n}"),
+                }
             }
             Err(err) => {
-                if let Ok(parse_error) = err.downcast::<ParseError>() {
-                    println!("  {parse_error}")
+                if err.is::<ParseError>() || err.is::<ParamError>() {
+                    println!("  {}", term::paint(&err.to_string(), &[Style::Red], color))
                 } else {
-                    println!("  Missing input!");
+                    println!("  {}", term::paint("Missing input!", &[Style::Red], color));
                     println!("  Place input file in {}", filepath.display());
                 }
             }
@@ -267,100 +589,543 @@ fn run(
 
     if totals {
         println!("⭐ {solved}");
-        println!("🕓 {:.03} s", duration.as_nanos() as f64 / 1e9);
+        println!("🕓 {}", term::duration(duration));
         println!();
     }
 
     Ok(())
 }
 
-fn submit(year: u32, day: u32, part: PuzzlePart) -> Result<(), Box<dyn Error>> {
-    let client = http_client()?;
+/// Reruns a day every time its source or input is saved. Each round rebuilds with cargo and runs
+/// the new binary with the same arguments, minus `--watch`.
+fn watch(
+    year: Option<Selection>,
+    day: Option<Selection>,
+    input_path_override: Option<PathBuf>,
+) -> Result<(), Box<dyn Error>> {
+    let (year, day) = single_day(year, day)?;
+    let input_path = input_path_override.unwrap_or_else(|| paths::input(year, day));
+    let watched = vec![paths::source(year, day), input_path];
+
+    let exe = env::current_exe()?;
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let mut last_modified = modified_times(&watched);
+    loop {
+        let mut build = process::Command::new("cargo");
+        build.current_dir(paths::project_root());
+        build.args(["build", "--quiet", "--no-default-features", "--features"]);
+        build.arg(features().join(","));
+        // Rebuild the same profile and features as this binary, so the rerun picks up the change
+        if !cfg!(debug_assertions) {
+            build.arg("--release");
+        }
 
-    let answer = {
-        let solutions = filtered_solutions(Some(year), Some(day));
-        if solutions.is_empty() {
-            Err(format!("No solution found for {year} Day {day:02}").into())
-        } else if solutions.len() > 1 {
-            Err(format!("Multiple solutions found for {year} Day {day:02}").into())
-        } else {
-            let Solution {
-                input_path,
-                wrapper,
-                ..
-            } = &solutions[0];
-            match wrapper(input_path.to_str().unwrap()) {
-                Ok((part1, part2)) => match part {
-                    PuzzlePart::P1 => {
-                        part1.ok_or(format!("Part 1 unsolved for {year} Day {day:02}").into())
+        if build.status()?.success() {
+            process::Command::new(&exe)
+                .args(&args)
+                .env(WATCH_CHILD_VAR, "1")
+                .status()?;
+        }
+
+        println!(
+            "Watching {} for changes...",
+            watched.iter().map(|path| path.display()).join(" and ")
+        );
+
+        last_modified = wait_for_change(|| watched.clone(), last_modified);
+        println!();
+    }
+}
+
+#[cfg(not(feature = "hot-reload"))]
+fn hot_run(
+    _: Option<Selection>,
+    _: Option<Selection>,
+    _: Option<PathBuf>,
+    _: &[(String, String)],
+) -> Result<(), Box<dyn Error>> {
+    Err("Hot reloading needs a build with `--features hot-reload`".into())
+}
+
+/// Like [`watch`], but keeps this process alive: each round only rebuilds the library as a cdylib,
+/// with just the year being run and none of the command line tool, and reloads it. The input is
+/// only reread when it changes.
+#[cfg(feature = "hot-reload")]
+fn hot_run(
+    year: Option<Selection>,
+    day: Option<Selection>,
+    input_path_override: Option<PathBuf>,
+    param_overrides: &[(String, String)],
+) -> Result<(), Box<dyn Error>> {
+    use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+    use std::time::Instant;
+
+    use aoc::hot::HotLibrary;
+
+    let (year, day) = single_day(year, day)?;
+
+    // Kept apart from the normal build, which the different crate type would otherwise invalidate
+    let target_dir = paths::project_root().join("target").join("hot");
+    let profile = if cfg!(debug_assertions) {
+        "debug"
+    } else {
+        "release"
+    };
+    let built_path = target_dir
+        .join(profile)
+        .join(format!("{DLL_PREFIX}aoc{DLL_SUFFIX}"));
+
+    let input_path = input_path_override
+        .clone()
+        .unwrap_or_else(|| paths::input(year, day));
+    // Listed afresh every time, so new source files are picked up
+    let watched = || -> Vec<PathBuf> {
+        let mut watched = vec![input_path.clone()];
+        watched.extend(source_files(&paths::project_root().join("src")).unwrap_or_default());
+        watched
+    };
+
+    let mut library: Option<HotLibrary> = None;
+    let mut loaded_path: Option<PathBuf> = None;
+    let mut input: Option<(Option<SystemTime>, String)> = None;
+    let mut generation = 0;
+
+    let mut last_modified = modified_times(&watched());
+    let color = term::color_enabled();
+    loop {
+        let mut build = process::Command::new("cargo");
+        build.current_dir(paths::project_root());
+        build.args([
+            "rustc",
+            "--lib",
+            "--quiet",
+            "--no-default-features",
+            "--features",
+        ]);
+        build.arg(format!("hot-reload,y{year}"));
+        build.arg("--target-dir").arg(&target_dir);
+        if !cfg!(debug_assertions) {
+            build.arg("--release");
+        }
+        build.args(["--crate-type", "cdylib"]);
+
+        if build.status()?.success() {
+            // A library can't be loaded twice from the same path, so each build gets a copy
+            generation += 1;
+            let next_path =
+                built_path.with_file_name(format!("{DLL_PREFIX}aoc-hot{generation}{DLL_SUFFIX}"));
+            fs::copy(&built_path, &next_path)?;
+
+            // Unload the old build first, in case the new one clashes with it. Its copy can only
+            // be deleted after that on some platforms
+            drop(library.take());
+            if let Some(old_path) = loaded_path.replace(next_path.clone())
+                && let Err(err) = fs::remove_file(&old_path)
+            {
+                println!("Failed to remove {}: {err}", old_path.display());
+            }
+
+            library = match HotLibrary::load(&next_path) {
+                Ok(library) => Some(library),
+                Err(err) => {
+                    println!("Failed to load {}: {err}", next_path.display());
+                    None
+                }
+            };
+        }
+
+        let input_modified = fs::metadata(&input_path)
+            .and_then(|meta| meta.modified())
+            .ok();
+        if input
+            .as_ref()
+            .is_none_or(|(modified, _)| *modified != input_modified)
+        {
+            input = fs::read_to_string(&input_path)
+                .ok()
+                .map(|text| (input_modified, text));
+        }
+
+        println!("{year} Day {day:02}");
+        match (&library, &input) {
+            (Some(library), Some((_, input))) => {
+                let instant = Instant::now();
+                match library.solve(year, day, input, param_overrides) {
+                    Ok((part1, part2)) => {
+                        let elapsed = instant.elapsed();
+
+                        let record = if input_path_override.is_none() && param_overrides.is_empty()
+                        {
+                            AnswerRecord::load(year, day).unwrap_or_default()
+                        } else {
+                            AnswerRecord::default()
+                        };
+
+                        println!("  Part 1: {}", check_answer(part1, record.part1, color));
+                        println!("  Part 2: {}", check_answer(part2, record.part2, color));
+                        println!("  Elapsed: {}", format_elapsed(elapsed, color));
                     }
-                    PuzzlePart::P2 => {
-                        part2.ok_or(format!("Part 2 unsolved for {year} Day {day:02}").into())
+                    Err(err) => {
+                        println!("  {}", term::paint(&err.to_string(), &[Style::Red], color))
                     }
-                },
-                Err(err) => Err(err),
+                }
+            }
+            (None, _) => println!(
+                "  {}",
+                term::paint("Library failed to build", &[Style::Red], color)
+            ),
+            (_, None) => {
+                println!("  {}", term::paint("Missing input!", &[Style::Red], color));
+                println!("  Place input file in {}", input_path.display());
             }
         }
-    }?;
+        println!();
+
+        println!("Watching src and {} for changes...", input_path.display());
+        last_modified = wait_for_change(watched, last_modified);
+        println!();
+    }
+}
+
+/// Every file under `dir`.
+#[cfg(feature = "hot-reload")]
+fn source_files(dir: &Path) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut files = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(source_files(&path)?);
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+/// When each file was last modified, by path so files coming and going count as changes too.
+fn modified_times(paths: &[PathBuf]) -> Vec<(PathBuf, Option<SystemTime>)> {
+    let mut times: Vec<_> = paths
+        .iter()
+        .map(|path| {
+            let modified = fs::metadata(path).and_then(|meta| meta.modified()).ok();
+            (path.clone(), modified)
+        })
+        .collect();
+    times.sort();
+    times
+}
+
+/// Polls until any of `paths` has been modified since `last_modified`, returning the new times.
+fn wait_for_change(
+    paths: impl Fn() -> Vec<PathBuf>,
+    last_modified: Vec<(PathBuf, Option<SystemTime>)>,
+) -> Vec<(PathBuf, Option<SystemTime>)> {
+    loop {
+        thread::sleep(WATCH_INTERVAL);
+        let modified = modified_times(&paths());
+        if modified != last_modified {
+            return modified;
+        }
+    }
+}
+
+/// Prints a row per solution: whether its input, puzzle description, example and recorded answer
+/// for each part exist, and its last recorded time on this machine. With `check`, each input is
+/// also solved to show which parts the solution answers.
+fn list(
+    year: Option<Selection>,
+    day: Option<Selection>,
+    check: bool,
+) -> Result<(), Box<dyn Error>> {
+    let machine = perf::machine_id();
+    let history = PerfLog::default().load()?;
+
+    let exists = |path: &Path| if path.exists() { "✓" } else { "·" };
+    let recorded = |answer: &Option<Answer>| if answer.is_some() { "✓" } else { "·" };
+
+    let mut header = format!(
+        "{:<12}  {:<5}  {:<6}  {:<7}  {:<8}  {:<8}",
+        "", "Input", "Puzzle", "Example", "Answer 1", "Answer 2"
+    );
+    if check {
+        header += &format!("  {:<8}  {:<8}", "Part 1", "Part 2");
+    }
+    println!("{header}  Last run");
+
+    for solution in filtered_solutions(year.as_ref(), day.as_ref()) {
+        let Solution { year, day, .. } = solution;
+        let record = AnswerRecord::load(year, day)?;
+
+        let mut row = format!(
+            "{year} Day {day:02}   {:<5}  {:<6}  {:<7}  {:<8}  {:<8}",
+            if solution.has_input() { "✓" } else { "·" },
+            exists(&paths::puzzle(year, day)),
+            exists(&paths::example(year, day)),
+            recorded(&record.part1),
+            recorded(&record.part2),
+        );
+
+        if check {
+            let (part1, part2) = if !solution.has_input() {
+                ("-", "-")
+            } else {
+                match solve_caught(&solution) {
+                    Ok(Report { part1, part2, .. }) => {
+                        let status = |part: Option<Answer>| match part {
+                            Some(_) => "solved",
+                            None => "unsolved",
+                        };
+                        (status(part1), status(part2))
+                    }
+                    Err(_) => ("error", "error"),
+                }
+            };
+            row += &format!("  {part1:<8}  {part2:<8}");
+        }
 
-    let mut params = HashMap::new();
-    match part {
-        PuzzlePart::P1 => params.insert("level", "1"),
-        PuzzlePart::P2 => params.insert("level", "2"),
+        let last_run = history
+            .iter()
+            .rev()
+            .find(|entry| entry.machine == machine && entry.year == year && entry.day == day)
+            .map_or("-".to_owned(), |entry| term::duration(entry.elapsed));
+
+        println!("{row}  {last_run}");
+    }
+
+    Ok(())
+}
+
+/// Solves a solution's input, turning panics into errors so one broken day doesn't end the listing.
+fn solve_caught(solution: &Solution) -> Result<Report, Box<dyn Error>> {
+    let params = Params::defaults(solution.solver.params());
+
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        solution.solve(&solution.input()?, &params)
+    }))
+    .unwrap_or_else(|_| Err("Solution panicked".into()))
+}
+
+/// Formats an answer, marking whether it matches the recorded one. Unsolved parts and wrong
+/// answers are coloured if `color` is on.
+fn check_answer(answer: Option<Answer>, expected: Option<Answer>, color: bool) -> String {
+    match (answer, expected) {
+        (None, _) => term::paint("unsolved", &[Style::Yellow], color),
+        (Some(answer), Some(expected)) if answer == expected => format!("{answer} ✓"),
+        (Some(answer), Some(expected)) => term::paint(
+            &format!("{answer} ✗ (expected {expected})"),
+            &[Style::Red],
+            color,
+        ),
+        // Grids get their own lines so they stay aligned
+        (Some(Answer::Grid(grid)), None) => format!("\n{grid}"),
+        (Some(answer), None) => answer.to_string(),
+    }
+}
+
+/// Formats how long a day took, highlighting it if it's slow and `color` is on.
+fn format_elapsed(elapsed: Duration, color: bool) -> String {
+    let styles: &[Style] = if elapsed > SLOW {
+        &[Style::Yellow]
+    } else {
+        &[]
+    };
+    term::paint(&term::duration(elapsed), styles, color)
+}
+
+fn perf_compare(rev: Option<String>, threshold: f64) -> Result<(), Box<dyn Error>> {
+    let machine = perf::machine_id();
+    let entries: Vec<perf::Entry> = PerfLog::default()
+        .load()?
+        .into_iter()
+        .filter(|entry| entry.machine == machine)
+        .collect();
+
+    let base = match rev {
+        Some(rev) => resolve_rev(&rev)?,
+        None => entries
+            .iter()
+            .rev()
+            .map(|entry| &entry.commit)
+            .find(|commit| *commit != perf::COMMIT)
+            .ok_or("No timings from other commits on this machine yet")?
+            .clone(),
     };
-    params.insert("answer", &answer);
 
-    let submit_url = format!("https://adventofcode.com/{year}/day/{day}/answer");
-    let r = client.post(&submit_url).form(&params).send()?;
-    let url = r.url().clone();
+    let before = perf::best_times(&entries, &base);
+    let after = perf::best_times(&entries, perf::COMMIT);
+    if before.is_empty() {
+        return Err(format!("No timings for {base} on this machine").into());
+    }
+    if after.is_empty() {
+        return Err("No timings for this build yet, run some solutions first".into());
+    }
+
+    println!("{} -> {}", short_commit(&base), short_commit(perf::COMMIT));
+
+    let mut regressions = 0;
+    for ((year, day), before) in &before {
+        let Some(after) = after.get(&(*year, *day)) else {
+            continue;
+        };
+
+        let change = (after.as_secs_f64() / before.as_secs_f64() - 1.0) * 100.0;
+        // Sub-millisecond differences are mostly noise
+        let regressed = change > threshold && after.saturating_sub(*before) > MIN_REGRESSION;
+        regressions += regressed as usize;
+
+        println!(
+            "{year} Day {day:02}  {} -> {}  {change:+.1}%{}",
+            term::duration(*before),
+            term::duration(*after),
+            if regressed { "  ✗ regression" } else { "" }
+        );
+    }
+
+    println!();
+    println!("{regressions} regression(s) above {threshold}%");
+
+    Ok(())
+}
+
+/// Resolves a git revision to the full commit hash that timings are recorded under.
+fn resolve_rev(rev: &str) -> Result<String, Box<dyn Error>> {
+    // The log's commits are the checkout's, wherever this is run from
+    let output = process::Command::new("git")
+        .current_dir(paths::project_root())
+        .args(["rev-parse", "--verify", &format!("{rev}^{{commit}}")])
+        .output()?;
+    if !output.status.success() {
+        return Err(format!("Unknown revision: {rev}").into());
+    }
+
+    Ok(String::from_utf8(output.stdout)?.trim().to_owned())
+}
 
-    let html = Html::parse_document(&r.text()?);
-    let selector = Selector::parse("article")?;
+/// Abbreviates a commit hash like git does, keeping any `-dirty` suffix.
+fn short_commit(commit: &str) -> String {
+    match commit.split_once('-') {
+        Some((hash, suffix)) => format!("{}-{suffix}", &hash[..hash.len().min(7)]),
+        None => commit[..commit.len().min(7)].to_owned(),
+    }
+}
 
-    let Some(puzzle_text) = html.select(&selector).next() else {
-        return Err(ArticleNotFoundError(url.to_string()).into());
+fn solution_for(year: u32, day: u32) -> Result<Solution, Box<dyn Error>> {
+    find_solution(year, day)
+        .ok_or_else(|| format!("No solution found for {year} Day {day:02}").into())
+}
+
+fn generate(
+    year: u32,
+    day: u32,
+    size: usize,
+    seed: Option<u64>,
+    output: Option<PathBuf>,
+) -> Result<(), Box<dyn Error>> {
+    let solution = solution_for(year, day)?;
+
+    let seed = seed.unwrap_or_else(|| {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        now.as_nanos() as u64
+    });
+    let Some(input) = solution.solver.generate(seed, size) else {
+        return Err(format!("No input generator for {year} Day {day:02}").into());
     };
 
-    let md = htmd::convert(&puzzle_text.html())?;
-
-    if md.contains("That's the right answer") {
-        println!("That's the right answer! Refreshing puzzle description...");
-        download_puzzle(&client, year, day, true)?;
-        Ok(())
-    } else if md.contains("That's not the right answer") {
-        print!("That's not the right answer");
-        if md.contains("too low") {
-            println!(", too low");
-        } else if md.contains("too high") {
-            println!(", too high");
+    let output = output.unwrap_or_else(|| paths::generated_input(year, day, seed, size));
+    if let Some(dir) = output.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&output, input)?;
+
+    println!("Wrote {} (seed {seed})", output.display());
+    println!(
+        "Run it with: run -y {year} -d {day} -i {}",
+        output.display()
+    );
+
+    Ok(())
+}
+
+/// Solves generated inputs with both the real and reference solutions, reporting the seeds of
+/// any where they disagree.
+fn cross_check(
+    year: u32,
+    day: u32,
+    size: usize,
+    runs: u64,
+    seed: u64,
+) -> Result<(), Box<dyn Error>> {
+    let solution = solution_for(year, day)?;
+    let color = term::color_enabled();
+
+    let mismatches = solution.cross_check(size, seed..seed + runs)?;
+    let count = mismatches.len();
+
+    for Mismatch {
+        seed,
+        expected,
+        actual,
+    } in mismatches
+    {
+        println!("Seed {seed}:");
+        match actual {
+            Ok((part1, part2)) => {
+                println!("  Part 1: {}", check_answer(part1, expected.0, color));
+                println!("  Part 2: {}", check_answer(part2, expected.1, color));
+            }
+            Err(err) => println!("  {err}"),
         }
-        Ok(())
-    } else if md.contains("You gave an answer too recently") {
-        println!("You gave an answer too recently");
-        Ok(())
-    } else if md.contains("You don't seem to be solving the right level") {
-        println!("You don't seem to be solving the right level");
-        Ok(())
-    } else {
-        Err("Failed to parse AoC response".into())
+        println!("  Reproduce with: gen {year} {day} {size} -s {seed}");
+    }
+
+    if count > 0 {
+        return Err(
+            format!("{count} of {runs} inputs disagreed with the reference solution").into(),
+        );
     }
+
+    println!("All {runs} inputs agreed with the reference solution");
+    Ok(())
 }
 
-fn http_client() -> Result<reqwest::blocking::Client, Box<dyn Error>> {
-    let Ok(session_token) = env::var("AOC_SESSION_TOKEN") else {
-        return Err(SessionTokenError.into());
+fn submit(base_url: Option<&str>, year: u32, day: u32, part: Part) -> Result<(), Box<dyn Error>> {
+    let answer: Answer = {
+        let solution = solution_for(year, day)?;
+        let params = Params::defaults(solution.solver.params());
+        // Only the part being submitted, so an unfinished other part can't get in the way
+        let Report { part1, part2, .. } =
+            solution.solve_probed(&solution.input()?, &params, Some(part), &mut ())?;
+        match part {
+            Part::One => part1,
+            Part::Two => part2,
+        }
+        .ok_or(format!(
+            "Part {} unsolved for {year} Day {day:02}",
+            part.number()
+        ))?
     };
 
-    let cookie = format!("session={session_token}");
-    let url: Url = "https://adventofcode.com".parse().unwrap();
+    let client = AocClient::from_env(base_url)?;
 
-    let jar = Jar::default();
-    jar.add_cookie_str(&cookie, &url);
+    match client.submit(year, day, part, &answer)? {
+        SubmitOutcome::Correct => {
+            println!("That's the right answer! Refreshing puzzle description...");
+            download_puzzle(&client, year, day, true)?;
+        }
+        SubmitOutcome::Incorrect(hint) => match hint {
+            Some(Hint::TooLow) => println!("That's not the right answer, too low"),
+            Some(Hint::TooHigh) => println!("That's not the right answer, too high"),
+            None => println!("That's not the right answer"),
+        },
+        SubmitOutcome::TooRecent(None) => println!("You gave an answer too recently"),
+        SubmitOutcome::TooRecent(Some(wait)) => {
+            println!("You gave an answer too recently, wait {wait:?}")
+        }
+        SubmitOutcome::WrongLevel => println!("You don't seem to be solving the right level"),
+    }
 
-    reqwest::blocking::Client::builder()
-        .user_agent("github.com/cynicalico/aoc cynicalico@pm.me")
-        .cookie_provider(jar.into())
-        .redirect(reqwest::redirect::Policy::none())
-        .build()
-        .map_err(|e| e.into())
+    Ok(())
 }
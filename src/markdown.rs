@@ -0,0 +1,355 @@
+use crate::term::{self, Style};
+
+const HEADING: &[Style] = &[Style::Bold, Style::Green];
+const EMPHASIS: &[Style] = &[Style::Bold, Style::BrightWhite];
+const CODE: &[Style] = &[Style::Cyan];
+const LINK: &[Style] = &[Style::Underline];
+const CODE_BLOCK_INDENT: &str = "    ";
+
+enum Block {
+    Heading(String),
+    Paragraph(String),
+    ListItem(String),
+    Code(Vec<String>),
+}
+
+/// A run of text that is styled the same way.
+struct Span {
+    text: String,
+    styles: &'static [Style],
+}
+
+/// Renders the markdown of a downloaded puzzle description for a terminal `width` columns wide.
+///
+/// Only the subset of markdown that puzzle pages produce is understood: headings, paragraphs,
+/// lists, code blocks, inline code, emphasis (AoC's highlighted text), and links. Without `color`,
+/// the markers for inline code and emphasis are left in place instead.
+pub fn render(md: &str, width: usize, color: bool) -> String {
+    let mut out = String::new();
+
+    let blocks = parse_blocks(md);
+    for (i, block) in blocks.iter().enumerate() {
+        match block {
+            Block::Heading(text) => {
+                out += &term::paint(text, HEADING, color);
+                out.push('\n');
+            }
+            Block::Paragraph(text) => out += &wrap(&spans(text, color), width, "", "", color),
+            Block::ListItem(text) => {
+                out += &wrap(&spans(text, color), width, "  • ", "    ", color)
+            }
+            Block::Code(lines) => {
+                for line in lines {
+                    out += CODE_BLOCK_INDENT;
+                    out += line;
+                    out.push('\n');
+                }
+            }
+        }
+
+        let list_continues = matches!(
+            (block, blocks.get(i + 1)),
+            (Block::ListItem(_), Some(Block::ListItem(_)))
+        );
+        if !list_continues {
+            out.push('\n');
+        }
+    }
+
+    out.truncate(out.trim_end().len());
+    out.push('\n');
+    out
+}
+
+fn parse_blocks(md: &str) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut lines = md.lines();
+    // Whether the next line can be a continuation of the last paragraph or list item
+    let mut in_text = false;
+
+    while let Some(line) = lines.next() {
+        let line = line.trim();
+
+        if line.is_empty() {
+            in_text = false;
+            continue;
+        }
+
+        if line.starts_with("```") {
+            let code = lines.by_ref().take_while(|l| !l.trim().starts_with("```"));
+            blocks.push(Block::Code(code.map(str::to_owned).collect()));
+            in_text = false;
+        } else if line.starts_with('#') {
+            blocks.push(Block::Heading(
+                line.trim_start_matches('#').trim().to_owned(),
+            ));
+            in_text = false;
+        } else if let Some(item) = list_item(line) {
+            blocks.push(Block::ListItem(item.to_owned()));
+            in_text = true;
+        } else if in_text
+            && let Some(Block::Paragraph(text) | Block::ListItem(text)) = blocks.last_mut()
+        {
+            text.push(' ');
+            text.push_str(line);
+        } else {
+            blocks.push(Block::Paragraph(line.to_owned()));
+            in_text = true;
+        }
+    }
+
+    blocks
+}
+
+fn list_item(line: &str) -> Option<&str> {
+    if let Some(item) = line.strip_prefix("* ").or_else(|| line.strip_prefix("- ")) {
+        return Some(item.trim_start());
+    }
+
+    let (number, item) = line.split_once(". ")?;
+    (!number.is_empty() && number.bytes().all(|b| b.is_ascii_digit())).then(|| item.trim_start())
+}
+
+/// Collects spans, merging consecutive unstyled characters.
+struct Spans {
+    spans: Vec<Span>,
+    plain: String,
+    color: bool,
+}
+
+impl Spans {
+    fn push_char(&mut self, c: char) { self.plain.push(c); }
+
+    /// Pushes styled text, or the text between `marker`s if colour is off.
+    fn push_styled(&mut self, text: String, styles: &'static [Style], marker: &str) {
+        self.flush();
+        if self.color {
+            self.spans.push(Span { text, styles });
+        } else {
+            self.plain = format!("{marker}{text}{marker}");
+            self.flush();
+        }
+    }
+
+    fn flush(&mut self) {
+        if !self.plain.is_empty() {
+            self.spans.push(Span {
+                text: std::mem::take(&mut self.plain),
+                styles: &[],
+            });
+        }
+    }
+
+    fn finish(mut self) -> Vec<Span> {
+        self.flush();
+        self.spans
+    }
+}
+
+/// Splits inline markdown into styled spans.
+fn spans(text: &str, color: bool) -> Vec<Span> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut spans = Spans {
+        spans: vec![],
+        plain: String::new(),
+        color,
+    };
+
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' if i + 1 < chars.len() => {
+                spans.push_char(chars[i + 1]);
+                i += 2;
+                continue;
+            }
+            '`' => {
+                if let Some(end) = find(&chars, i + 1, |j| chars[j] == '`') {
+                    let code: String = chars[i + 1..end].iter().collect();
+                    // AoC highlights parts of code with `<em>`, which comes out as `_..._`
+                    match code.strip_prefix('_').and_then(|c| c.strip_suffix('_')) {
+                        Some(em) if !em.is_empty() => {
+                            spans.push_styled(em.to_owned(), EMPHASIS, "`")
+                        }
+                        _ => spans.push_styled(code, CODE, "`"),
+                    }
+                    i = end + 1;
+                    continue;
+                }
+            }
+            '_' if i == 0 || !chars[i - 1].is_alphanumeric() => {
+                let closes = |j: usize| {
+                    chars[j] == '_' && chars.get(j + 1).is_none_or(|c| !c.is_alphanumeric())
+                };
+                if let Some(end) = find(&chars, i + 2, closes) {
+                    let em = unescape(&chars[i + 1..end]).replace('`', "");
+                    spans.push_styled(em, EMPHASIS, "_");
+                    i = end + 1;
+                    continue;
+                }
+            }
+            '[' => {
+                let closes = |j: usize| chars[j] == ']' && chars[j - 1] != '\\';
+                if let Some(mid) = find(&chars, i + 1, closes)
+                    && chars.get(mid + 1) == Some(&'(')
+                    && let Some(end) = find(&chars, mid + 2, |j| chars[j] == ')')
+                {
+                    spans.push_styled(unescape(&chars[i + 1..mid]), LINK, "");
+                    i = end + 1;
+                    continue;
+                }
+            }
+            _ => (),
+        }
+
+        spans.push_char(chars[i]);
+        i += 1;
+    }
+
+    spans.finish()
+}
+
+fn find(chars: &[char], from: usize, pred: impl Fn(usize) -> bool) -> Option<usize> {
+    (from..chars.len()).find(|&j| pred(j))
+}
+
+fn unescape(chars: &[char]) -> String {
+    let mut s = String::new();
+    let mut escaped = false;
+    for &c in chars {
+        if c == '\\' && !escaped {
+            escaped = true;
+        } else {
+            s.push(c);
+            escaped = false;
+        }
+    }
+    s
+}
+
+/// Greedily wraps `spans` to `width`, starting the first line with `first` and the others with
+/// `rest`.
+fn wrap(spans: &[Span], width: usize, first: &str, rest: &str, color: bool) -> String {
+    // Each word is a list of differently styled pieces, e.g. "`0`)." is code followed by text
+    let mut words: Vec<Vec<(String, &[Style])>> = vec![vec![]];
+    for span in spans {
+        for (i, piece) in span.text.split(' ').enumerate() {
+            if i > 0 {
+                words.push(vec![]);
+            }
+            if !piece.is_empty() {
+                words
+                    .last_mut()
+                    .unwrap()
+                    .push((piece.to_owned(), span.styles));
+            }
+        }
+    }
+
+    let mut out = first.to_owned();
+    let mut line_len = first.chars().count();
+    let mut at_line_start = true;
+
+    for word in words.iter().filter(|w| !w.is_empty()) {
+        let word_len: usize = word.iter().map(|(piece, _)| piece.chars().count()).sum();
+
+        if !at_line_start && line_len + 1 + word_len > width {
+            out.push('\n');
+            out += rest;
+            line_len = rest.chars().count();
+            at_line_start = true;
+        }
+
+        if !at_line_start {
+            out.push(' ');
+            line_len += 1;
+        }
+
+        for (piece, styles) in word {
+            out += &term::paint(piece, styles, color);
+        }
+
+        line_len += word_len;
+        at_line_start = false;
+    }
+
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn styled(words: &str, styles: &[Style]) -> String {
+        words
+            .split(' ')
+            .map(|word| term::paint(word, styles, true))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn emphasis_inside_code() {
+        let md = "The answer is `_42_`, not `x + _y_`.";
+
+        assert_eq!(
+            render(md, 80, true),
+            format!(
+                "The answer is {}, not {}.\n",
+                styled("42", EMPHASIS),
+                styled("x + _y_", CODE)
+            )
+        );
+        assert_eq!(
+            render(md, 80, false),
+            "The answer is `42`, not `x + _y_`.\n"
+        );
+    }
+
+    #[test]
+    fn code_inside_emphasis() {
+        assert_eq!(
+            render("Count _`(` and `)`_ only.", 80, true),
+            format!("Count {} only.\n", styled("( and )", EMPHASIS))
+        );
+    }
+
+    #[test]
+    fn underscores_inside_words() {
+        assert_eq!(render("snake_case_name", 80, true), "snake_case_name\n");
+    }
+
+    #[test]
+    fn links() {
+        let md = "Go [back to \\[Calendar\\]](/2015) now.";
+
+        assert_eq!(
+            render(md, 80, true),
+            format!("Go {} now.\n", styled("back to [Calendar]", LINK))
+        );
+        assert_eq!(render(md, 80, false), "Go back to [Calendar] now.\n");
+    }
+
+    #[test]
+    fn list_items_continue_onto_following_lines() {
+        let md = "Rules:\n\n* first item\n  carries on\n* second item\n\nAfterwards.";
+
+        assert_eq!(
+            render(md, 80, false),
+            "Rules:\n\n  • first item carries on\n  • second item\n\nAfterwards.\n"
+        );
+    }
+
+    #[test]
+    fn wrapping() {
+        let md = "## Day 1\n\nSanta is trying to deliver presents.\n\n1. up one floor each \
+                  time\n\n```\n((()))\n```";
+
+        assert_eq!(
+            render(md, 16, false),
+            "Day 1\n\nSanta is trying\nto deliver\npresents.\n\n  • up one floor\n    each \
+             time\n\n    ((()))\n"
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// A named value a solution depends on that differs between the examples and the real input, e.g.
+/// a grid size or number of iterations.
+#[derive(Debug)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: &'static str,
+    pub help: &'static str,
+}
+
+#[derive(Debug)]
+pub struct ParamError(String);
+
+impl Error for ParamError {}
+
+impl fmt::Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid parameter: {}", self.0)
+    }
+}
+
+/// The parameter values for a run: a solution's declared defaults, with any overrides applied.
+#[derive(Debug, Clone, Default)]
+pub struct Params {
+    values: HashMap<&'static str, String>,
+}
+
+impl Params {
+    pub fn defaults(declared: &'static [Param]) -> Self {
+        Self {
+            values: declared
+                .iter()
+                .map(|param| (param.name, param.default.to_owned()))
+                .collect(),
+        }
+    }
+
+    /// Applies `name=value` overrides on top of the defaults, rejecting names that weren't declared.
+    pub fn new(declared: &'static [Param], overrides: &[(&str, &str)]) -> Result<Self, ParamError> {
+        let mut params = Self::defaults(declared);
+
+        for (name, value) in overrides {
+            let Some(param) = declared.iter().find(|param| param.name == *name) else {
+                let available: Vec<&str> = declared.iter().map(|param| param.name).collect();
+                return Err(ParamError(format!(
+                    "'{name}' isn't declared by this solution (available: {})",
+                    if available.is_empty() {
+                        "none".to_owned()
+                    } else {
+                        available.join(", ")
+                    }
+                )));
+            };
+            params.values.insert(param.name, (*value).to_owned());
+        }
+
+        Ok(params)
+    }
+
+    pub fn get<T: FromStr>(&self, name: &str) -> Result<T, ParamError> {
+        let Some(value) = self.values.get(name) else {
+            return Err(ParamError(format!(
+                "'{name}' isn't declared by this solution"
+            )));
+        };
+
+        value
+            .parse()
+            .map_err(|_| ParamError(format!("'{value}' isn't a valid value for '{name}'")))
+    }
+}
+
+/// Parses a `name=value` command line argument.
+pub fn parse_override(s: &str) -> Result<(String, String), ParamError> {
+    match s.split_once('=') {
+        Some((name, value)) => Ok((name.trim().to_owned(), value.trim().to_owned())),
+        None => Err(ParamError(format!("expected name=value, got '{s}'"))),
+    }
+}
@@ -0,0 +1,63 @@
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// The checkout this binary was built from, where `src/` and `templates/` live. Falls back to the
+/// current directory if the binary has been moved away from it.
+pub fn project_root() -> PathBuf {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    if root.is_dir() {
+        root.to_owned()
+    } else {
+        PathBuf::from(".")
+    }
+}
+
+/// Where inputs, puzzles, answers and other downloaded or recorded data live: `$AOC_DATA_DIR` if
+/// set, otherwise the project root.
+pub fn data_dir() -> PathBuf {
+    match env::var_os("AOC_DATA_DIR") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => project_root(),
+    }
+}
+
+/// `dir/yYYYY/dayDD.ext`, the layout used for everything kept per day.
+fn day_file(dir: PathBuf, year: u32, day: u32, extension: &str) -> PathBuf {
+    dir.join(format!("y{year}"))
+        .join(format!("day{day:02}"))
+        .with_extension(extension)
+}
+
+pub fn input(year: u32, day: u32) -> PathBuf {
+    day_file(data_dir().join("input"), year, day, "txt")
+}
+
+pub fn puzzle(year: u32, day: u32) -> PathBuf {
+    day_file(data_dir().join("puzzle"), year, day, "md")
+}
+
+pub fn example(year: u32, day: u32) -> PathBuf {
+    day_file(data_dir().join("puzzle"), year, day, "example.txt")
+}
+
+pub fn answer(year: u32, day: u32) -> PathBuf {
+    day_file(data_dir().join("answer"), year, day, "txt")
+}
+
+/// A generated input, named after how it was generated so runs can be reproduced.
+pub fn generated_input(year: u32, day: u32, seed: u64, size: usize) -> PathBuf {
+    day_file(
+        data_dir().join("input"),
+        year,
+        day,
+        &format!("gen-{seed}-{size}.txt"),
+    )
+}
+
+pub fn source(year: u32, day: u32) -> PathBuf {
+    day_file(project_root().join("src"), year, day, "rs")
+}
+
+pub fn http_cache() -> PathBuf { data_dir().join("cache").join("http") }
+
+pub fn perf_history() -> PathBuf { data_dir().join("perf").join("history.tsv") }
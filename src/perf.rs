@@ -0,0 +1,159 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{env, fmt};
+
+use crate::paths;
+
+/// The commit this binary was built from, see `build.rs`.
+pub const COMMIT: &str = env!("AOC_GIT_COMMIT");
+
+/// One timing of a solution on its real input.
+#[derive(Debug, Clone)]
+pub struct Entry {
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub commit: String,
+    pub machine: String,
+    pub year: u32,
+    pub day: u32,
+    /// Time spent solving, i.e. [`Timings::total`](crate::Timings::total), not reading the input
+    pub elapsed: Duration,
+}
+
+#[derive(Debug)]
+pub struct BadEntryError(String);
+
+impl Error for BadEntryError {}
+
+impl fmt::Display for BadEntryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Malformed perf history entry: '{}'", self.0)
+    }
+}
+
+impl Entry {
+    /// A timing taken now, by this build on this machine.
+    pub fn now(year: u32, day: u32, elapsed: Duration) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        Self {
+            timestamp,
+            commit: COMMIT.to_owned(),
+            machine: machine_id(),
+            year,
+            day,
+            elapsed,
+        }
+    }
+
+    fn parse(line: &str) -> Result<Self, BadEntryError> {
+        let bad = || BadEntryError(line.to_owned());
+
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [timestamp, commit, machine, year, day, nanos] = fields[..] else {
+            return Err(bad());
+        };
+
+        Ok(Self {
+            timestamp: timestamp.parse().map_err(|_| bad())?,
+            commit: commit.to_owned(),
+            machine: machine.to_owned(),
+            year: year.parse().map_err(|_| bad())?,
+            day: day.parse().map_err(|_| bad())?,
+            elapsed: Duration::from_nanos(nanos.parse().map_err(|_| bad())?),
+        })
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            self.timestamp,
+            self.commit,
+            self.machine,
+            self.year,
+            self.day,
+            self.elapsed.as_nanos()
+        )
+    }
+}
+
+/// Identifies this machine, so timings from different hardware aren't compared. Uses
+/// `AOC_MACHINE_ID` if set, then `/etc/machine-id`, then the host name.
+pub fn machine_id() -> String {
+    if let Ok(id) = env::var("AOC_MACHINE_ID") {
+        return id;
+    }
+    if let Ok(id) = fs::read_to_string("/etc/machine-id") {
+        return id.trim().to_owned();
+    }
+
+    env::var("HOSTNAME")
+        .or_else(|_| env::var("COMPUTERNAME"))
+        .unwrap_or_else(|_| "unknown".to_owned())
+}
+
+/// Append-only log of every timing, one tab-separated [`Entry`] per line.
+pub struct PerfLog {
+    path: PathBuf,
+}
+
+impl Default for PerfLog {
+    fn default() -> Self { Self::new(paths::perf_history()) }
+}
+
+impl PerfLog {
+    pub fn new(path: PathBuf) -> Self { Self { path } }
+
+    pub fn append(&self, entry: &Entry) -> Result<(), Box<dyn Error>> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{entry}")?;
+
+        Ok(())
+    }
+
+    pub fn load(&self) -> Result<Vec<Entry>, Box<dyn Error>> {
+        if !fs::exists(&self.path)? {
+            return Ok(vec![]);
+        }
+
+        let log = fs::read_to_string(&self.path)?;
+        let entries = log
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(Entry::parse)
+            .collect::<Result<_, _>>()?;
+
+        Ok(entries)
+    }
+}
+
+/// The fastest time for each day in `entries` from `commit`. The fastest is the least disturbed by
+/// whatever else the machine was doing.
+pub fn best_times(entries: &[Entry], commit: &str) -> BTreeMap<(u32, u32), Duration> {
+    let mut best: BTreeMap<(u32, u32), Duration> = BTreeMap::new();
+
+    for entry in entries.iter().filter(|entry| entry.commit == commit) {
+        best.entry((entry.year, entry.day))
+            .and_modify(|time| *time = (*time).min(entry.elapsed))
+            .or_insert(entry.elapsed);
+    }
+
+    best
+}
@@ -0,0 +1,68 @@
+use std::sync::LazyLock;
+
+use scraper::{ElementRef, Html, Selector};
+
+static ARTICLE: LazyLock<Selector> = LazyLock::new(|| Selector::parse("article.day-desc").unwrap());
+static TITLE: LazyLock<Selector> = LazyLock::new(|| Selector::parse("h2").unwrap());
+static CODE_BLOCK: LazyLock<Selector> = LazyLock::new(|| Selector::parse("pre > code").unwrap());
+static PARAGRAPH: LazyLock<Selector> = LazyLock::new(|| Selector::parse("main > p").unwrap());
+static CODE: LazyLock<Selector> = LazyLock::new(|| Selector::parse("code").unwrap());
+
+/// A downloaded puzzle page.
+pub struct PuzzlePage {
+    html: Html,
+}
+
+impl PuzzlePage {
+    pub fn parse(html: &str) -> Self {
+        Self {
+            html: Html::parse_document(html),
+        }
+    }
+
+    /// The description of each unlocked part, converted to markdown.
+    pub fn markdown(&self) -> Vec<String> {
+        self.articles()
+            .filter_map(|article| htmd::convert(&article.html()).ok())
+            .collect()
+    }
+
+    /// The puzzle title, e.g. "Not Quite Lisp" from "--- Day 1: Not Quite Lisp ---".
+    pub fn title(&self) -> Option<String> {
+        let heading = self
+            .articles()
+            .next()?
+            .select(&TITLE)
+            .next()?
+            .text()
+            .collect::<String>();
+        let heading = heading.trim().trim_matches('-').trim();
+
+        Some(match heading.split_once(": ") {
+            Some((_, title)) => title.to_owned(),
+            None => heading.to_owned(),
+        })
+    }
+
+    /// The first code block in part 1, which is almost always the example input.
+    pub fn example(&self) -> Option<String> {
+        let code = self.articles().next()?.select(&CODE_BLOCK).next()?;
+        Some(code.text().collect())
+    }
+
+    /// The accepted answers shown under each solved part, in order.
+    pub fn answers(&self) -> Vec<String> {
+        self.html
+            .select(&PARAGRAPH)
+            .filter(|p| {
+                p.text()
+                    .collect::<String>()
+                    .starts_with("Your puzzle answer was")
+            })
+            .filter_map(|p| p.select(&CODE).next())
+            .map(|code| code.text().collect())
+            .collect()
+    }
+
+    fn articles(&self) -> impl Iterator<Item = ElementRef<'_>> { self.html.select(&ARTICLE) }
+}
@@ -0,0 +1,62 @@
+use std::time::{Duration, Instant};
+
+use crate::util::viz;
+use crate::{Answer, Answers, Phase, Probe};
+
+/// How long each phase of a solver took.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration { self.parse + self.part1 + self.part2 }
+}
+
+/// The answers from solving one input, and how long it took.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub year: u32,
+    pub day: u32,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+    pub timings: Timings,
+}
+
+/// A generated input on which a solver disagreed with its reference solution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub seed: u64,
+    pub expected: Answers,
+    /// The solver's answers, or why it failed to produce any
+    pub actual: Result<Answers, String>,
+}
+
+/// Records [`Timings`] for each phase of a solver, leaving out time spent recording frames.
+#[derive(Debug, Default)]
+pub struct TimingProbe {
+    pub timings: Timings,
+    started: Option<(Instant, Duration)>,
+}
+
+impl Probe for TimingProbe {
+    fn enter(&mut self, _: Phase) { self.started = Some((Instant::now(), viz::recording_time())); }
+
+    fn exit(&mut self, phase: Phase) {
+        let elapsed = self
+            .started
+            .take()
+            .map_or(Duration::ZERO, |(started, recording)| {
+                started
+                    .elapsed()
+                    .saturating_sub(viz::recording_time() - recording)
+            });
+        match phase {
+            Phase::Parse => self.timings.parse = elapsed,
+            Phase::Part1 => self.timings.part1 = elapsed,
+            Phase::Part2 => self.timings.part2 = elapsed,
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// A set of years or days picked on the command line, as a comma-separated list of numbers and
+/// ranges, e.g. `3,7,9`, `1..=5`, or `2015..2017` (which, like in Rust, stops before 2017).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selection {
+    ranges: Vec<RangeInclusive<u32>>,
+}
+
+#[derive(Debug)]
+pub struct SelectionError(String);
+
+impl Error for SelectionError {}
+
+impl fmt::Display for SelectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid selection: '{}'", self.0)
+    }
+}
+
+impl Selection {
+    pub fn contains(&self, n: u32) -> bool { self.ranges.iter().any(|range| range.contains(&n)) }
+
+    /// The only number selected, if there's exactly one.
+    pub fn single(&self) -> Option<u32> {
+        match self.ranges[..] {
+            [ref range] if range.start() == range.end() => Some(*range.start()),
+            _ => None,
+        }
+    }
+}
+
+impl From<u32> for Selection {
+    fn from(n: u32) -> Self {
+        Self {
+            ranges: vec![n..=n],
+        }
+    }
+}
+
+impl FromStr for Selection {
+    type Err = SelectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bad = || SelectionError(s.to_owned());
+        let number = |n: &str| n.trim().parse::<u32>().map_err(|_| bad());
+
+        let ranges = s
+            .split(',')
+            .map(|part| {
+                let range = if let Some((start, end)) = part.split_once("..=") {
+                    number(start)?..=number(end)?
+                } else if let Some((start, end)) = part.split_once("..") {
+                    number(start)?..=number(end)?.checked_sub(1).ok_or_else(bad)?
+                } else {
+                    let n = number(part)?;
+                    n..=n
+                };
+
+                if range.is_empty() {
+                    Err(bad())
+                } else {
+                    Ok(range)
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { ranges })
+    }
+}
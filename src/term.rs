@@ -0,0 +1,110 @@
+use std::env;
+use std::io::{self, IsTerminal};
+use std::time::Duration;
+
+use terminal_size::Width;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Style {
+    Bold,
+    Dim,
+    Underline,
+    Red,
+    Green,
+    Yellow,
+    Cyan,
+    BrightWhite,
+}
+
+impl Style {
+    fn sgr(self) -> &'static str {
+        match self {
+            Style::Bold => "1",
+            Style::Dim => "2",
+            Style::Underline => "4",
+            Style::Red => "31",
+            Style::Green => "32",
+            Style::Yellow => "33",
+            Style::Cyan => "36",
+            Style::BrightWhite => "97",
+        }
+    }
+}
+
+/// Whether stdout should be coloured: it has to be a terminal, and `NO_COLOR` must be unset.
+pub fn color_enabled() -> bool {
+    env::var_os("NO_COLOR").is_none_or(|v| v.is_empty()) && io::stdout().is_terminal()
+}
+
+/// Terminal width: `COLUMNS` if set, otherwise the width of the terminal on stdout, or 80 if stdout
+/// isn't a terminal.
+pub fn width() -> usize {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.parse().ok())
+        .or_else(|| terminal_size::terminal_size().map(|(Width(w), _)| w as usize))
+        .unwrap_or(80)
+}
+
+/// Formats a duration in whichever of ns, µs, ms or s keeps it to a few significant digits, e.g.
+/// `842 µs` or `1.27 s`.
+pub fn duration(d: Duration) -> String {
+    let nanos = d.as_nanos();
+    if nanos < 1_000 {
+        return format!("{nanos} ns");
+    }
+
+    let decimals = |value: f64| -> usize {
+        match value {
+            ..10.0 => 2,
+            ..100.0 => 1,
+            _ => 0,
+        }
+    };
+
+    let mut value = nanos as f64;
+    for unit in ["µs", "ms", "s"] {
+        value /= 1e3;
+        // Rounded before settling on a unit, so 999.6 µs reads `1.00 ms` rather than `1000 µs`
+        let scale = 10f64.powi(decimals(value) as i32);
+        let rounded = (value * scale).round() / scale;
+        if rounded < 1000.0 || unit == "s" {
+            return format!("{:.*} {unit}", decimals(rounded), rounded);
+        }
+    }
+    unreachable!()
+}
+
+/// Wraps `s` in the escape codes for `styles`, or returns it untouched if `color` is off.
+pub fn paint(s: &str, styles: &[Style], color: bool) -> String {
+    if !color || styles.is_empty() || s.is_empty() {
+        return s.to_owned();
+    }
+
+    let sgr: Vec<&str> = styles.iter().map(|style| style.sgr()).collect();
+    format!("\x1b[{}m{s}\x1b[0m", sgr.join(";"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations() {
+        let cases = [
+            (Duration::from_nanos(999), "999 ns"),
+            (Duration::from_nanos(1_000), "1.00 µs"),
+            (Duration::from_nanos(9_996), "10.0 µs"),
+            (Duration::from_nanos(99_960), "100 µs"),
+            (Duration::from_nanos(842_000), "842 µs"),
+            (Duration::from_nanos(999_600), "1.00 ms"),
+            (Duration::from_nanos(999_999_999), "1.00 s"),
+            (Duration::from_millis(1_270), "1.27 s"),
+            (Duration::from_secs(1_500), "1500 s"),
+        ];
+
+        for (d, expected) in cases {
+            assert_eq!(duration(d), expected, "{d:?}");
+        }
+    }
+}
@@ -0,0 +1,189 @@
+// Glyphs taken from https://github.com/bsoyka/advent-of-code-ocr
+
+const SMALL_HEIGHT: usize = 6;
+const LARGE_HEIGHT: usize = 10;
+
+/// Columns from the start of one letter to the start of the next. Letters are split by position
+/// rather than by the blank columns between them, because some (like the small "Y") run into the
+/// gap.
+const SMALL_PITCH: usize = 5;
+const LARGE_PITCH: usize = 8;
+
+#[rustfmt::skip]
+const SMALL_FONT: &[(char, [&str; SMALL_HEIGHT])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+#[rustfmt::skip]
+const LARGE_FONT: &[(char, [&str; LARGE_HEIGHT])] = &[
+    ('A', ["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', ["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', [".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', [".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', ["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', ["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', ["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', ["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', ["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', ["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', ["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', ["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', ["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+/// Reads letters drawn with `#` (any other character is off), one row per line.
+pub fn read_str(s: &str) -> Option<String> {
+    let grid: Vec<Vec<bool>> = s
+        .lines()
+        .map(|line| line.chars().map(|c| c == '#').collect())
+        .collect();
+
+    read_grid(&grid)
+}
+
+/// Reads letters drawn in either of AoC's fonts: 6 pixels tall (letters are mostly 4 wide), or 10
+/// pixels tall (6 wide). Blank rows around the letters are ignored, and rows may be ragged.
+///
+/// Returns `None` if the height doesn't match a font or any letter isn't recognised.
+pub fn read_grid<R: AsRef<[bool]>>(grid: &[R]) -> Option<String> {
+    let lit_rows: Vec<usize> = (0..grid.len())
+        .filter(|&y| grid[y].as_ref().contains(&true))
+        .collect();
+    let rows = &grid[*lit_rows.first()?..=*lit_rows.last()?];
+
+    let pitch = match rows.len() {
+        SMALL_HEIGHT => SMALL_PITCH,
+        LARGE_HEIGHT => LARGE_PITCH,
+        _ => return None,
+    };
+
+    let width = rows.iter().map(|row| row.as_ref().len()).max()?;
+    let lit = |x: usize, y: usize| rows[y].as_ref().get(x).copied().unwrap_or(false);
+    let column_lit = |x: usize| (0..rows.len()).any(|y| lit(x, y));
+    let first = (0..width).find(|&x| column_lit(x))?;
+    let last = (0..width).rfind(|&x| column_lit(x))?;
+
+    let read_cell = |x: usize| {
+        let cell: Vec<String> = (0..rows.len())
+            .map(|y| {
+                (x..x + pitch)
+                    .map(|x| if lit(x, y) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+
+        match rows.len() {
+            SMALL_HEIGHT => lookup(SMALL_FONT, &cell),
+            _ => lookup(LARGE_FONT, &cell),
+        }
+    };
+
+    // Letters like "J" start with blank columns, so the first lit column isn't necessarily where
+    // the first cell starts
+    (0..pitch)
+        .filter_map(|back| first.checked_sub(back))
+        .find_map(|start| (start..=last).step_by(pitch).map(read_cell).collect())
+}
+
+/// Finds the letter whose glyph fills `cell`, with nothing else lit in the rest of it.
+fn lookup<const H: usize>(font: &[(char, [&str; H])], cell: &[String]) -> Option<char> {
+    font.iter()
+        .find(|(_, pixels)| {
+            pixels.iter().zip(cell).all(|(row, cell_row)| {
+                cell_row
+                    .strip_prefix(row)
+                    .is_some_and(|rest| rest.bytes().all(|b| b == b'.'))
+            })
+        })
+        .map(|(c, _)| *c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_small_font() {
+        let grid = "\
+####.####.####.#...##..#.####.###..####..###...##
+#....#....#....#...##.#..#....#..#.#......#.....#
+###..###..###...#.#.##...###..#..#.###....#.....#
+#....#....#......#..#.#..#....###..#......#.....#
+#....#....#......#..#.#..#....#.#..#......#..#..#
+####.#....####...#..#..#.#....#..#.#.....###..##";
+
+        assert_eq!(read_str(grid).as_deref(), Some("EFEYKFRFIJ"));
+    }
+
+    #[test]
+    fn reads_large_font() {
+        let grid = "\
+######....##....######..######..#####...#.......#....#.....###
+.....#...#..#...#............#..#....#..#.......#....#......#
+.....#..#....#..#............#..#....#..#........#..#.......#
+....#...#....#..#...........#...#....#..#........#..#.......#
+...#....#....#..#####......#....#####...#.........##........#
+..#.....######..#.........#.....#..#....#.........##........#
+.#......#....#..#........#......#...#...#........#..#.......#
+#.......#....#..#.......#.......#...#...#........#..#...#...#
+#.......#....#..#.......#.......#....#..#.......#....#..#...#
+######..#....#..######..######..#....#..######..#....#...###";
+
+        assert_eq!(read_str(grid).as_deref(), Some("ZAEZRLXJ"));
+    }
+
+    #[test]
+    fn reads_letters_that_touch() {
+        // "Y" is 5 wide, so there's no blank column between it and the "A"
+        let grid = "\
+#...#.##
+#...##..#
+.#.#.#..#
+..#..####
+..#..#..#
+..#..#..#";
+
+        assert_eq!(read_str(grid).as_deref(), Some("YA"));
+    }
+
+    #[test]
+    fn reads_letters_starting_with_blank_columns() {
+        // Padded with blank rows, and the "J" leaves the first two columns blank
+        let grid = "
+..............
+..##..###.####
+...#...#..#
+...#...#..###
+...#...#..#
+#..#...#..#
+.##...###.####
+";
+
+        assert_eq!(read_str(grid).as_deref(), Some("JIE"));
+    }
+
+    #[test]
+    fn rejects_unknown_shapes() {
+        assert_eq!(read_str("#\n#\n#"), None);
+        assert_eq!(read_str("#.#.\n.#..\n#.#.\n.#..\n#.#.\n.#.."), None);
+    }
+}
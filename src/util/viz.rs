@@ -0,0 +1,201 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::term;
+
+/// Delay between frames when animating in the terminal.
+const FRAME_DELAY: Duration = Duration::from_millis(40);
+/// Terminal characters from darkest to brightest.
+const SHADES: [char; 5] = [' ', '░', '▒', '▓', '█'];
+
+/// Where frames go.
+#[derive(Debug, Clone)]
+pub enum Target {
+    /// Numbered PGM/PPM files in a directory
+    Dir(PathBuf),
+    /// Redrawn in place in the terminal
+    Terminal,
+}
+
+static TARGET: Mutex<Option<Target>> = Mutex::new(None);
+static FRAMES: AtomicUsize = AtomicUsize::new(0);
+static RECORDING_NANOS: AtomicU64 = AtomicU64::new(0);
+
+/// Sets where the frames of the solutions that run next go, or turns visualisation off.
+pub fn set_target(target: Option<Target>) {
+    *TARGET.lock().unwrap() = target;
+    FRAMES.store(0, Ordering::Relaxed);
+}
+
+/// Number of frames written since the target was last set.
+pub fn frame_count() -> usize { FRAMES.load(Ordering::Relaxed) }
+
+/// Total time spent recording frames, so timings can leave it out. Only ever grows.
+pub fn recording_time() -> Duration {
+    Duration::from_nanos(RECORDING_NANOS.load(Ordering::Relaxed))
+}
+
+/// Returns a recorder for a sequence of frames called `name`, or `None` if visualisation is off,
+/// so solutions only pay for drawing when asked to.
+pub fn recorder(name: &str) -> Option<Recorder> {
+    let target = TARGET.lock().unwrap().clone()?;
+
+    Some(Recorder {
+        name: name.to_owned(),
+        target,
+        frame: 0,
+    })
+}
+
+pub struct Recorder {
+    name: String,
+    target: Target,
+    frame: usize,
+}
+
+impl Recorder {
+    /// Records a greyscale frame, shading each cell from 0 (black) to 255 (white).
+    pub fn gray<R: AsRef<[T]>, T>(&mut self, grid: &[R], shade: impl Fn(&T) -> u8) {
+        let started = Instant::now();
+        let image = Image::new(grid, |cell| [shade(cell); 3]);
+        self.record(&image, "pgm", started);
+    }
+
+    /// Records a colour frame, painting each cell with an RGB triple.
+    pub fn color<R: AsRef<[T]>, T>(&mut self, grid: &[R], paint: impl Fn(&T) -> [u8; 3]) {
+        let started = Instant::now();
+        let image = Image::new(grid, paint);
+        self.record(&image, "ppm", started);
+    }
+
+    fn record(&mut self, image: &Image, extension: &str, started: Instant) {
+        let result = match &self.target {
+            Target::Dir(dir) => {
+                let path = dir
+                    .join(format!("{}-{:05}", self.name, self.frame))
+                    .with_extension(extension);
+                image.write_netpbm(&path, extension == "ppm")
+            }
+            Target::Terminal => image.draw(&self.name, self.frame),
+        };
+
+        // A failed frame shouldn't take the solution down with it
+        if let Err(err) = result {
+            eprintln!(
+                "Failed to record frame {} of {}: {err}",
+                self.frame, self.name
+            );
+        }
+
+        self.frame += 1;
+        FRAMES.fetch_add(1, Ordering::Relaxed);
+        RECORDING_NANOS.fetch_add(started.elapsed().as_nanos() as u64, Ordering::Relaxed);
+    }
+}
+
+/// Lays out sparse points as a grid just big enough to hold them, filling the gaps with `blank`.
+pub fn plot<T: Copy>(points: impl IntoIterator<Item = ((i64, i64), T)>, blank: T) -> Vec<Vec<T>> {
+    let points: Vec<_> = points.into_iter().collect();
+
+    let Some(min_x) = points.iter().map(|((x, _), _)| *x).min() else {
+        return vec![];
+    };
+    let max_x = points.iter().map(|((x, _), _)| *x).max().unwrap();
+    let min_y = points.iter().map(|((_, y), _)| *y).min().unwrap();
+    let max_y = points.iter().map(|((_, y), _)| *y).max().unwrap();
+
+    let width = (max_x - min_x + 1) as usize;
+    let height = (max_y - min_y + 1) as usize;
+
+    let mut grid = vec![vec![blank; width]; height];
+    for ((x, y), cell) in points {
+        grid[(y - min_y) as usize][(x - min_x) as usize] = cell;
+    }
+
+    grid
+}
+
+struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<[u8; 3]>,
+}
+
+impl Image {
+    fn new<R: AsRef<[T]>, T>(grid: &[R], paint: impl Fn(&T) -> [u8; 3]) -> Self {
+        let width = grid.iter().map(|row| row.as_ref().len()).max().unwrap_or(0);
+
+        let mut pixels = Vec::with_capacity(width * grid.len());
+        for row in grid {
+            let row = row.as_ref();
+            pixels.extend(row.iter().map(&paint));
+            // Ragged rows are padded with black
+            pixels.extend((row.len()..width).map(|_| [0; 3]));
+        }
+
+        Self {
+            width,
+            height: grid.len(),
+            pixels,
+        }
+    }
+
+    /// Writes a binary PPM if `color`, otherwise a PGM.
+    fn write_netpbm(&self, path: &Path, color: bool) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut out = BufWriter::new(File::create(path)?);
+        let magic = if color { "P6" } else { "P5" };
+        write!(out, "{magic}\n{} {}\n255\n", self.width, self.height)?;
+
+        for pixel in &self.pixels {
+            if color {
+                out.write_all(pixel)?;
+            } else {
+                out.write_all(&pixel[..1])?;
+            }
+        }
+
+        out.flush()
+    }
+
+    /// Redraws the terminal with the image, scaled down to fit its width. Each character covers a
+    /// block of pixels twice as tall as it is wide, shaded by their average brightness.
+    fn draw(&self, name: &str, frame: usize) -> io::Result<()> {
+        let scale = self.width.div_ceil(term::width()).max(1);
+        let (block_w, block_h) = (scale, scale * 2);
+
+        let mut out = format!("\x1b[H\x1b[2J{name} #{frame}\n");
+        for by in (0..self.height).step_by(block_h) {
+            for bx in (0..self.width).step_by(block_w) {
+                let (mut total, mut count) = (0, 0);
+                for y in by..(by + block_h).min(self.height) {
+                    for x in bx..(bx + block_w).min(self.width) {
+                        total += luma(self.pixels[y * self.width + x]);
+                        count += 1;
+                    }
+                }
+                out.push(SHADES[total * SHADES.len() / (count * 256)]);
+            }
+            out.push('\n');
+        }
+
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(out.as_bytes())?;
+        stdout.flush()?;
+
+        thread::sleep(FRAME_DELAY);
+        Ok(())
+    }
+}
+
+fn luma([r, g, b]: [u8; 3]) -> usize {
+    (r as usize * 299 + g as usize * 587 + b as usize * 114) / 1000
+}
@@ -1,31 +1,42 @@
 use std::collections::HashMap;
 use std::error::Error;
 
-pub type ParsedInput<'a> = &'a str;
+use crate::{Day, Params};
 
-pub fn parse(input: &str) -> Result<ParsedInput, Box<dyn Error>> { Ok(input) }
+pub struct Day01;
 
-pub fn part1(input: &ParsedInput) -> Option<i32> {
-    let counts: HashMap<char, usize> = input.chars().fold(HashMap::default(), |mut counts, c| {
-        *counts.entry(c).or_insert(0) += 1;
-        counts
-    });
+impl Day for Day01 {
+    type Input<'a> = &'a str;
+    type Part1 = i32;
+    type Part2 = usize;
 
-    Some(counts[&'('] as i32 - counts[&')'] as i32)
-}
+    fn parse<'a>(input: &'a str, _: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        Ok(input)
+    }
 
-pub fn part2(input: &ParsedInput) -> Option<usize> {
-    let mut floor = 0;
-    for (i, c) in input.char_indices() {
-        match c {
-            '(' => floor += 1,
-            ')' => floor -= 1,
-            _ => panic!("Unexpected character in input string: '{c}'"),
-        }
-        if floor == -1 {
-            return Some(i + 1);
-        }
+    fn part1(input: &Self::Input<'_>) -> Option<i32> {
+        let counts: HashMap<char, usize> =
+            input.chars().fold(HashMap::default(), |mut counts, c| {
+                *counts.entry(c).or_insert(0) += 1;
+                counts
+            });
+
+        Some(counts[&'('] as i32 - counts[&')'] as i32)
     }
 
-    panic!("Santa never enters the basement");
+    fn part2(input: &Self::Input<'_>, _: Option<&i32>) -> Option<usize> {
+        let mut floor = 0;
+        for (i, c) in input.char_indices() {
+            match c {
+                '(' => floor += 1,
+                ')' => floor -= 1,
+                _ => panic!("Unexpected character in input string: '{c}'"),
+            }
+            if floor == -1 {
+                return Some(i + 1);
+            }
+        }
+
+        panic!("Santa never enters the basement");
+    }
 }
@@ -3,32 +3,39 @@ use std::error::Error;
 
 use crate::util::array::CastExt;
 use crate::util::parse::ParseOps;
+use crate::{Day, Params};
 
-pub type ParsedInput = Vec<[u32; 3]>;
+pub struct Day02;
 
-pub fn parse(input: &str) -> Result<ParsedInput, Box<dyn Error>> {
-    Ok(input
-        .lines()
-        .map(|line| {
-            line.iter_unsigned::<u32>()
-                .cast()
-                .expect("lines are formatted LxWxH")
-        })
-        .collect())
-}
+impl Day for Day02 {
+    type Input<'a> = Vec<[u32; 3]>;
+    type Part1 = u32;
+    type Part2 = u32;
 
-pub fn part1(input: &ParsedInput) -> Option<u32> {
-    Some(input.iter().fold(0, |acc, [l, w, h]| {
-        let surface_area = 2 * l * w + 2 * w * h + 2 * h * l;
-        let min_side_area = cmp::min(cmp::min(l * w, w * h), h * l);
-        acc + surface_area + min_side_area
-    }))
-}
+    fn parse<'a>(input: &'a str, _: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        Ok(input
+            .lines()
+            .map(|line| {
+                line.iter_unsigned::<u32>()
+                    .cast()
+                    .expect("lines are formatted LxWxH")
+            })
+            .collect())
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Option<u32> {
+        Some(input.iter().fold(0, |acc, [l, w, h]| {
+            let surface_area = 2 * l * w + 2 * w * h + 2 * h * l;
+            let min_side_area = cmp::min(cmp::min(l * w, w * h), h * l);
+            acc + surface_area + min_side_area
+        }))
+    }
 
-pub fn part2(input: &ParsedInput) -> Option<u32> {
-    Some(input.iter().fold(0, |acc, [l, w, h]| {
-        let min_perimeter = cmp::min(cmp::min(2 * (l + w), 2 * (w + h)), 2 * (h + l));
-        let volume = l * w * h;
-        acc + min_perimeter + volume
-    }))
+    fn part2(input: &Self::Input<'_>, _: Option<&u32>) -> Option<u32> {
+        Some(input.iter().fold(0, |acc, [l, w, h]| {
+            let min_perimeter = cmp::min(cmp::min(2 * (l + w), 2 * (w + h)), 2 * (h + l));
+            let volume = l * w * h;
+            acc + min_perimeter + volume
+        }))
+    }
 }
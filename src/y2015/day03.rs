@@ -1,6 +1,9 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 
+use crate::util::viz;
+use crate::{Day, Params};
+
 #[derive(Copy, Clone)]
 pub enum Direction {
     North,
@@ -9,21 +12,6 @@ pub enum Direction {
     West,
 }
 
-pub type ParsedInput = Vec<Direction>;
-
-pub fn parse(input: &str) -> Result<ParsedInput, Box<dyn Error>> {
-    Ok(input
-        .chars()
-        .map(|c| match c {
-            '^' => Direction::North,
-            'v' => Direction::South,
-            '>' => Direction::East,
-            '<' => Direction::West,
-            _ => unreachable!("Input contains only ^v<>"),
-        })
-        .collect())
-}
-
 fn move_in_dir(pos: (i32, i32), dir: Direction) -> (i32, i32) {
     match dir {
         Direction::North => (pos.0, pos.1 - 1),
@@ -33,34 +21,82 @@ fn move_in_dir(pos: (i32, i32), dir: Direction) -> (i32, i32) {
     }
 }
 
-pub fn part1(input: &ParsedInput) -> Option<usize> {
-    let mut visited: HashSet<(i32, i32)> = HashSet::from([(0, 0)]);
+pub struct Day03;
 
-    let mut santa_pos = (0, 0);
+impl Day for Day03 {
+    type Input<'a> = Vec<Direction>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    for dir in input {
-        santa_pos = move_in_dir(santa_pos, *dir);
-        visited.insert(santa_pos);
+    fn parse<'a>(input: &'a str, _: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        Ok(input
+            .chars()
+            .map(|c| match c {
+                '^' => Direction::North,
+                'v' => Direction::South,
+                '>' => Direction::East,
+                '<' => Direction::West,
+                _ => unreachable!("Input contains only ^v<>"),
+            })
+            .collect())
     }
 
-    visited.len().into()
-}
-
-pub fn part2(input: &ParsedInput) -> Option<usize> {
-    let mut visited: HashSet<(i32, i32)> = HashSet::from([(0, 0)]);
+    fn part1(input: &Self::Input<'_>) -> Option<usize> {
+        let mut visited: HashSet<(i32, i32)> = HashSet::from([(0, 0)]);
 
-    let mut santa_pos = (0, 0);
-    let mut robo_santa_pos = (0, 0);
+        let mut santa_pos = (0, 0);
 
-    for (i, dir) in input.iter().enumerate() {
-        if i % 2 == 0 {
+        for dir in input {
             santa_pos = move_in_dir(santa_pos, *dir);
             visited.insert(santa_pos);
-        } else {
-            robo_santa_pos = move_in_dir(robo_santa_pos, *dir);
-            visited.insert(robo_santa_pos);
         }
+
+        if let Some(mut viz) = viz::recorder("part1") {
+            let houses = visited.iter().map(|&(x, y)| ((x as i64, y as i64), true));
+            viz.gray(&viz::plot(houses, false), |&v| if v { 255 } else { 0 });
+        }
+
+        visited.len().into()
     }
 
-    visited.len().into()
+    fn part2(input: &Self::Input<'_>, _: Option<&usize>) -> Option<usize> {
+        let mut visited: HashSet<(i32, i32)> = HashSet::from([(0, 0)]);
+        // Who visited each house, only tracked when visualising
+        let mut visitors: HashMap<(i32, i32), [bool; 2]> = HashMap::new();
+        let mut viz = viz::recorder("part2");
+
+        let mut santa_pos = (0, 0);
+        let mut robo_santa_pos = (0, 0);
+
+        for (i, dir) in input.iter().enumerate() {
+            if i % 2 == 0 {
+                santa_pos = move_in_dir(santa_pos, *dir);
+                visited.insert(santa_pos);
+            } else {
+                robo_santa_pos = move_in_dir(robo_santa_pos, *dir);
+                visited.insert(robo_santa_pos);
+            }
+
+            if viz.is_some() {
+                let pos = if i % 2 == 0 {
+                    santa_pos
+                } else {
+                    robo_santa_pos
+                };
+                visitors.entry(pos).or_default()[i % 2] = true;
+            }
+        }
+
+        if let Some(viz) = &mut viz {
+            let houses = visitors
+                .iter()
+                .map(|(&(x, y), &v)| ((x as i64, y as i64), v));
+            // Santa's houses are red, Robo-Santa's green, and ones they both visited yellow
+            viz.color(&viz::plot(houses, [false; 2]), |&[santa, robo]| {
+                [if santa { 255 } else { 0 }, if robo { 255 } else { 0 }, 0]
+            });
+        }
+
+        visited.len().into()
+    }
 }
@@ -2,30 +2,41 @@ use std::error::Error;
 
 use md5::{Digest, Md5};
 
-pub type ParsedInput<'a> = &'a str;
+use crate::{Day, Params};
 
-pub fn parse(input: &str) -> Result<ParsedInput, Box<dyn Error>> { Ok(input.trim()) }
+pub struct Day04;
 
-pub fn part1(input: &ParsedInput) -> Option<u32> {
-    for ans in 1.. {
-        let hash = Md5::new_with_prefix(input)
-            .chain_update(ans.to_string())
-            .finalize();
-        if hash[0] == 0 && hash[1] == 0 && (hash[2] >> 4) & 0xf == 0 {
-            return Some(ans);
+impl Day for Day04 {
+    type Input<'a> = &'a str;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse<'a>(input: &'a str, _: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        Ok(input.trim())
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Option<u32> {
+        for ans in 1.. {
+            let hash = Md5::new_with_prefix(input)
+                .chain_update(ans.to_string())
+                .finalize();
+            if hash[0] == 0 && hash[1] == 0 && (hash[2] >> 4) & 0xf == 0 {
+                return Some(ans);
+            }
         }
+        unreachable!();
     }
-    unreachable!();
-}
 
-pub fn part2(input: &ParsedInput) -> Option<u32> {
-    for ans in 1.. {
-        let hash = Md5::new_with_prefix(input)
-            .chain_update(ans.to_string())
-            .finalize();
-        if hash[0] == 0 && hash[1] == 0 && hash[2] == 0 {
-            return Some(ans);
+    fn part2(input: &Self::Input<'_>, part1: Option<&u32>) -> Option<u32> {
+        // Six leading zeroes implies five, so there's no point searching below part 1's answer
+        for ans in part1.copied().unwrap_or(1).. {
+            let hash = Md5::new_with_prefix(input)
+                .chain_update(ans.to_string())
+                .finalize();
+            if hash[0] == 0 && hash[1] == 0 && hash[2] == 0 {
+                return Some(ans);
+            }
         }
+        unreachable!();
     }
-    unreachable!();
 }
@@ -3,9 +3,8 @@ use std::error::Error;
 
 use itertools::izip;
 
-pub type ParsedInput<'a> = Vec<&'a str>;
-
-pub fn parse(input: &str) -> Result<ParsedInput, Box<dyn Error>> { Ok(input.lines().collect()) }
+use crate::util::rng::Rng;
+use crate::{Day, Params};
 
 fn is_string_nice_p1(s: &str) -> bool {
     let mut vowel_count = 0;
@@ -29,9 +28,6 @@ fn is_string_nice_p1(s: &str) -> bool {
 
     vowel_count >= 3 && double_letter_count >= 1
 }
-pub fn part1(input: &ParsedInput) -> Option<usize> {
-    input.iter().filter(|s| is_string_nice_p1(s)).count().into()
-}
 
 fn is_string_nice_p2(s: &str) -> bool {
     let mut pairs: HashMap<(char, char), Vec<usize>> = HashMap::new();
@@ -63,6 +59,56 @@ fn is_string_nice_p2(s: &str) -> bool {
         })
 }
 
-pub fn part2(input: &ParsedInput) -> Option<usize> {
-    input.iter().filter(|s| is_string_nice_p2(s)).count().into()
+pub struct Day05;
+
+impl Day for Day05 {
+    type Input<'a> = Vec<&'a str>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse<'a>(input: &'a str, _: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        Ok(input.lines().collect())
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Option<usize> {
+        input.iter().filter(|s| is_string_nice_p1(s)).count().into()
+    }
+
+    fn part2(input: &Self::Input<'_>, _: Option<&usize>) -> Option<usize> {
+        input.iter().filter(|s| is_string_nice_p2(s)).count().into()
+    }
+
+    /// `size` short strings over a small alphabet, so pairs and repeats are common.
+    fn generate(seed: u64, size: usize) -> Option<String> {
+        let mut rng = Rng::new(seed);
+        let alphabet = b"abcdeipquxy";
+
+        let lines: Vec<String> = (0..size)
+            .map(|_| {
+                let len = rng.range(1..20);
+                (0..len).map(|_| *rng.choose(alphabet) as char).collect()
+            })
+            .collect();
+
+        Some(lines.join("\n") + "\n")
+    }
+
+    fn reference(input: &str, _: &Params) -> Option<(Option<usize>, Option<usize>)> {
+        let nice_p1 = |s: &str| {
+            let b = s.as_bytes();
+            s.chars().filter(|c| "aeiou".contains(*c)).count() >= 3
+                && b.windows(2).any(|w| w[0] == w[1])
+                && !["ab", "cd", "pq", "xy"].iter().any(|bad| s.contains(bad))
+        };
+        let nice_p2 = |s: &str| {
+            let b = s.as_bytes();
+            (0..b.len().saturating_sub(1)).any(|i| s[i + 2..].contains(&s[i..i + 2]))
+                && b.windows(3).any(|w| w[0] == w[2])
+        };
+
+        Some((
+            Some(input.lines().filter(|s| nice_p1(s)).count()),
+            Some(input.lines().filter(|s| nice_p2(s)).count()),
+        ))
+    }
 }
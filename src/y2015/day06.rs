@@ -2,6 +2,9 @@ use std::error::Error;
 
 use crate::util::array::CastExt;
 use crate::util::parse::ParseOps;
+use crate::util::rng::Rng;
+use crate::util::viz;
+use crate::{Day, Param, Params, ParseError};
 
 pub enum Action {
     On,
@@ -11,69 +14,124 @@ pub enum Action {
 
 pub type Coord = (usize, usize);
 
-pub type ParsedInput = Vec<(Action, Coord, Coord)>;
-
-pub fn parse(input: &str) -> Result<ParsedInput, Box<dyn Error>> {
-    Ok(input
-        .lines()
-        .map(|l| {
-            let action = if l.starts_with("turn on") {
-                Action::On
-            } else if l.starts_with("turn off") {
-                Action::Off
-            } else if l.starts_with("toggle") {
-                Action::Toggle
-            } else {
-                unreachable!("valid actions are 'turn on', 'turn off', or 'toggle'")
-            };
-
-            let [x0, y0, x1, y1] = l.iter_unsigned().cast().expect("2 coords means 4 integers");
-
-            (action, (x0, y0), (x1, y1))
-        })
-        .collect::<ParsedInput>())
+pub struct Instructions {
+    size: usize,
+    steps: Vec<(Action, Coord, Coord)>,
 }
 
-pub fn part1(input: &ParsedInput) -> Option<usize> {
-    let mut lights = vec![vec![false; 1000]; 1000];
+pub struct Day06;
 
-    for (action, (x0, y0), (x1, y1)) in input {
-        for row in &mut lights[*y0..=*y1] {
-            for l in &mut row[*x0..=*x1] {
-                match action {
-                    Action::On => *l = true,
-                    Action::Off => *l = false,
-                    Action::Toggle => *l = !*l,
+impl Day for Day06 {
+    type Input<'a> = Instructions;
+    type Part1 = usize;
+    type Part2 = u64;
+
+    const PARAMS: &'static [Param] = &[Param {
+        name: "size",
+        default: "1000",
+        help: "Width and height of the grid of lights",
+    }];
+
+    fn parse<'a>(input: &'a str, params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        let steps = input
+            .lines()
+            .map(|l| {
+                let action = if l.starts_with("turn on") {
+                    Action::On
+                } else if l.starts_with("turn off") {
+                    Action::Off
+                } else if l.starts_with("toggle") {
+                    Action::Toggle
+                } else {
+                    unreachable!("valid actions are 'turn on', 'turn off', or 'toggle'")
                 };
+
+                let [x0, y0, x1, y1] = l.iter_unsigned().cast().expect("2 coords means 4 integers");
+
+                (action, (x0, y0), (x1, y1))
+            })
+            .collect::<Vec<(Action, Coord, Coord)>>();
+
+        let size: usize = params.get("size")?;
+        if let Some((_, _, (x1, y1))) = steps.iter().find(|(_, _, (x1, y1))| *x1.max(y1) >= size) {
+            return Err(ParseError(format!("{x1},{y1} is outside a {size}x{size} grid")).into());
+        }
+
+        Ok(Instructions { size, steps })
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Option<usize> {
+        let mut lights = vec![vec![false; input.size]; input.size];
+        let mut viz = viz::recorder("part1");
+
+        for (action, (x0, y0), (x1, y1)) in &input.steps {
+            for row in &mut lights[*y0..=*y1] {
+                for l in &mut row[*x0..=*x1] {
+                    match action {
+                        Action::On => *l = true,
+                        Action::Off => *l = false,
+                        Action::Toggle => *l = !*l,
+                    };
+                }
+            }
+
+            if let Some(viz) = &mut viz {
+                viz.gray(&lights, |&l| if l { 255 } else { 0 });
             }
         }
+
+        lights
+            .into_iter()
+            .map(|row| row.iter().filter(|&&l| l).count())
+            .sum::<usize>()
+            .into()
     }
 
-    lights
-        .into_iter()
-        .map(|row| row.iter().filter(|&&l| l).count())
-        .sum::<usize>()
-        .into()
-}
+    fn part2(input: &Self::Input<'_>, _: Option<&usize>) -> Option<u64> {
+        let mut lights = vec![vec![0u64; input.size]; input.size];
+        let mut viz = viz::recorder("part2");
 
-pub fn part2(input: &ParsedInput) -> Option<u64> {
-    let mut lights = vec![vec![0u64; 1000]; 1000];
+        for (action, (x0, y0), (x1, y1)) in &input.steps {
+            for row in &mut lights[*y0..=*y1] {
+                for l in &mut row[*x0..=*x1] {
+                    match action {
+                        Action::On => *l += 1,
+                        Action::Off => *l = if *l > 0 { *l - 1 } else { 0 },
+                        Action::Toggle => *l += 2,
+                    };
+                }
+            }
 
-    for (action, (x0, y0), (x1, y1)) in input {
-        for row in &mut lights[*y0..=*y1] {
-            for l in &mut row[*x0..=*x1] {
-                match action {
-                    Action::On => *l += 1,
-                    Action::Off => *l = if *l > 0 { *l - 1 } else { 0 },
-                    Action::Toggle => *l += 2,
-                };
+            if let Some(viz) = &mut viz {
+                viz.gray(&lights, |&l| (l * 8).min(255) as u8);
             }
         }
+
+        lights
+            .into_iter()
+            .map(|row| row.iter().sum::<u64>())
+            .sum::<u64>()
+            .into()
     }
 
-    lights
-        .into_iter()
-        .map(|row| row.iter().sum::<u64>())
-        .sum::<u64>()
-        .into()
+    /// `size` instructions for the default 1000x1000 grid.
+    fn generate(seed: u64, size: usize) -> Option<String> {
+        let mut rng = Rng::new(seed);
+        let mut lines = vec![];
+
+        for _ in 0..size.max(1) {
+            let action = rng.choose(&["turn on", "turn off", "toggle"]);
+            let (x0, x1) = (rng.range(0..1000), rng.range(0..1000));
+            let (y0, y1) = (rng.range(0..1000), rng.range(0..1000));
+            lines.push(format!(
+                "{action} {},{} through {},{}",
+                x0.min(x1),
+                y0.min(y1),
+                x0.max(x1),
+                y0.max(y1)
+            ));
+        }
+
+        Some(lines.join("\n") + "\n")
+    }
 }
@@ -3,8 +3,9 @@ use std::error::Error;
 
 use regex::Regex;
 
-use crate::ParseError;
 use crate::util::parse::ParseOps;
+use crate::util::rng::Rng;
+use crate::{Day, Param, Params, ParseError};
 
 #[derive(Debug, Copy, Clone)]
 pub enum Op {
@@ -19,88 +20,6 @@ pub enum Op {
     Not(usize),
 }
 
-pub type ParsedInput = (HashMap<String, usize>, Vec<Op>);
-
-pub fn parse(input: &str) -> Result<ParsedInput, Box<dyn Error>> {
-    const CONSTANT: &str = r"(?P<constant>[a-z]+|\d+)";
-    const AND: &str = r"(?P<lhs_and>[a-z]+|\d+) AND (?P<rhs_and>[a-z]+)";
-    const OR: &str = r"(?P<lhs_or>[a-z]+) OR (?P<rhs_or>[a-z]+)";
-    const LSHIFT: &str = r"(?P<lhs_lshift>[a-z]+) LSHIFT (?P<rhs_lshift>\d+)";
-    const RSHIFT: &str = r"(?P<lhs_rshift>[a-z]+) RSHIFT (?P<rhs_rshift>\d+)";
-    const NOT: &str = r"NOT (?P<rhs_not>[a-z]+)";
-    const DST_ID: &str = r"(?P<dst_wire>[a-z]+)";
-
-    let pat = format!(r"(?:{CONSTANT}|{AND}|{OR}|{LSHIFT}|{RSHIFT}|{NOT}) -> {DST_ID}");
-    let re = Regex::new(&pat).unwrap();
-
-    let mut id_map: HashMap<String, usize> = Default::default();
-    let mut state: Vec<Op> = Default::default();
-
-    let mut wire_to_id = |state: &mut Vec<Op>, wire: &str| -> usize {
-        let curr_size = id_map.len();
-        let id = *id_map.entry(wire.to_owned()).or_insert(curr_size);
-        if state.len() <= id {
-            state.resize(id + 1, Op::None);
-        }
-        id
-    };
-
-    for caps in re.captures_iter(input) {
-        if let Some(constant) = caps.name("constant") {
-            let constant_u64: Option<u64> = constant.as_str().parse().ok();
-            if let Some(constant_u64) = constant_u64 {
-                let dst_id = wire_to_id(&mut state, &caps["dst_wire"]);
-                state[dst_id] = Op::Const(constant_u64);
-            } else {
-                let dst_id = wire_to_id(&mut state, &caps["dst_wire"]);
-                let const_id = wire_to_id(&mut state, constant.as_str());
-                state[dst_id] = Op::ConstId(const_id);
-            }
-        } else if let Some(lhs) = caps.name("lhs_and")
-            && let Some(rhs) = caps.name("rhs_and")
-        {
-            let lhs_const: Option<u64> = lhs.as_str().parse().ok();
-            if let Some(lhs_const) = lhs_const {
-                let dst_id = wire_to_id(&mut state, &caps["dst_wire"]);
-                let rhs_id = wire_to_id(&mut state, rhs.as_str());
-                state[dst_id] = Op::ConstAnd(lhs_const, rhs_id);
-            } else {
-                let dst_id = wire_to_id(&mut state, &caps["dst_wire"]);
-                let lhs_id = wire_to_id(&mut state, lhs.as_str());
-                let rhs_id = wire_to_id(&mut state, rhs.as_str());
-                state[dst_id] = Op::And(lhs_id, rhs_id);
-            }
-        } else if let Some(lhs) = caps.name("lhs_or")
-            && let Some(rhs) = caps.name("rhs_or")
-        {
-            let dst_id = wire_to_id(&mut state, &caps["dst_wire"]);
-            let lhs_id = wire_to_id(&mut state, lhs.as_str());
-            let rhs_id = wire_to_id(&mut state, rhs.as_str());
-            state[dst_id] = Op::Or(lhs_id, rhs_id);
-        } else if let Some(lhs) = caps.name("lhs_lshift")
-            && let Some(rhs) = caps.name("rhs_lshift")
-        {
-            let dst_id = wire_to_id(&mut state, &caps["dst_wire"]);
-            let lhs_id = wire_to_id(&mut state, lhs.as_str());
-            state[dst_id] = Op::LShift(lhs_id, rhs.as_str().unsigned());
-        } else if let Some(lhs) = caps.name("lhs_rshift")
-            && let Some(rhs) = caps.name("rhs_rshift")
-        {
-            let dst_id = wire_to_id(&mut state, &caps["dst_wire"]);
-            let lhs_id = wire_to_id(&mut state, lhs.as_str());
-            state[dst_id] = Op::RShift(lhs_id, rhs.as_str().unsigned());
-        } else if let Some(rhs) = caps.name("rhs_not") {
-            let dst_id = wire_to_id(&mut state, &caps["dst_wire"]);
-            let rhs_id = wire_to_id(&mut state, rhs.as_str());
-            state[dst_id] = Op::Not(rhs_id);
-        } else {
-            return Err(ParseError(format!("Bad input: {}", caps[0].to_owned())).into());
-        }
-    }
-
-    Ok((id_map, state))
-}
-
 fn calc(state: &mut [Op], id: usize) -> u64 {
     let v = match state[id] {
         Op::None => unreachable!(),
@@ -117,17 +36,185 @@ fn calc(state: &mut [Op], id: usize) -> u64 {
     v
 }
 
-pub fn part1(input: &ParsedInput) -> Option<u64> {
-    let (id_map, mut state) = input.to_owned();
+/// Names of generated wires: "aa", "ab", ..., "zz", "aaa", etc. They're at least two letters long so
+/// they never clash with "a" and "b".
+fn wire_name(i: usize) -> String {
+    let mut n = i + 27;
+    let mut name = vec![];
+    while n > 0 {
+        n -= 1;
+        name.push(b'a' + (n % 26) as u8);
+        n /= 26;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap()
+}
 
-    calc(&mut state, id_map["a"]).into()
+pub struct Circuit {
+    state: Vec<Op>,
+    /// The wire whose signal is the answer
+    wire: usize,
+    /// The wire part 2 overrides with part 1's answer, if the circuit has it
+    override_wire: Option<usize>,
 }
 
-pub fn part2(input: &ParsedInput) -> Option<u64> {
-    let (id_map, mut state) = input.to_owned();
+pub struct Day07;
+
+impl Day for Day07 {
+    type Input<'a> = Circuit;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "wire",
+            default: "a",
+            help: "Wire whose signal is measured",
+        },
+        Param {
+            name: "override",
+            default: "b",
+            help: "Wire that part 2 overrides with part 1's signal",
+        },
+    ];
+
+    fn parse<'a>(input: &'a str, params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        const CONSTANT: &str = r"(?P<constant>[a-z]+|\d+)";
+        const AND: &str = r"(?P<lhs_and>[a-z]+|\d+) AND (?P<rhs_and>[a-z]+)";
+        const OR: &str = r"(?P<lhs_or>[a-z]+) OR (?P<rhs_or>[a-z]+)";
+        const LSHIFT: &str = r"(?P<lhs_lshift>[a-z]+) LSHIFT (?P<rhs_lshift>\d+)";
+        const RSHIFT: &str = r"(?P<lhs_rshift>[a-z]+) RSHIFT (?P<rhs_rshift>\d+)";
+        const NOT: &str = r"NOT (?P<rhs_not>[a-z]+)";
+        const DST_ID: &str = r"(?P<dst_wire>[a-z]+)";
+
+        let pat = format!(r"(?:{CONSTANT}|{AND}|{OR}|{LSHIFT}|{RSHIFT}|{NOT}) -> {DST_ID}");
+        let re = Regex::new(&pat).unwrap();
+
+        let mut id_map: HashMap<String, usize> = Default::default();
+        let mut state: Vec<Op> = Default::default();
+
+        let mut wire_to_id = |state: &mut Vec<Op>, wire: &str| -> usize {
+            let curr_size = id_map.len();
+            let id = *id_map.entry(wire.to_owned()).or_insert(curr_size);
+            if state.len() <= id {
+                state.resize(id + 1, Op::None);
+            }
+            id
+        };
+
+        for caps in re.captures_iter(input) {
+            if let Some(constant) = caps.name("constant") {
+                let constant_u64: Option<u64> = constant.as_str().parse().ok();
+                if let Some(constant_u64) = constant_u64 {
+                    let dst_id = wire_to_id(&mut state, &caps["dst_wire"]);
+                    state[dst_id] = Op::Const(constant_u64);
+                } else {
+                    let dst_id = wire_to_id(&mut state, &caps["dst_wire"]);
+                    let const_id = wire_to_id(&mut state, constant.as_str());
+                    state[dst_id] = Op::ConstId(const_id);
+                }
+            } else if let Some(lhs) = caps.name("lhs_and")
+                && let Some(rhs) = caps.name("rhs_and")
+            {
+                let lhs_const: Option<u64> = lhs.as_str().parse().ok();
+                if let Some(lhs_const) = lhs_const {
+                    let dst_id = wire_to_id(&mut state, &caps["dst_wire"]);
+                    let rhs_id = wire_to_id(&mut state, rhs.as_str());
+                    state[dst_id] = Op::ConstAnd(lhs_const, rhs_id);
+                } else {
+                    let dst_id = wire_to_id(&mut state, &caps["dst_wire"]);
+                    let lhs_id = wire_to_id(&mut state, lhs.as_str());
+                    let rhs_id = wire_to_id(&mut state, rhs.as_str());
+                    state[dst_id] = Op::And(lhs_id, rhs_id);
+                }
+            } else if let Some(lhs) = caps.name("lhs_or")
+                && let Some(rhs) = caps.name("rhs_or")
+            {
+                let dst_id = wire_to_id(&mut state, &caps["dst_wire"]);
+                let lhs_id = wire_to_id(&mut state, lhs.as_str());
+                let rhs_id = wire_to_id(&mut state, rhs.as_str());
+                state[dst_id] = Op::Or(lhs_id, rhs_id);
+            } else if let Some(lhs) = caps.name("lhs_lshift")
+                && let Some(rhs) = caps.name("rhs_lshift")
+            {
+                let dst_id = wire_to_id(&mut state, &caps["dst_wire"]);
+                let lhs_id = wire_to_id(&mut state, lhs.as_str());
+                state[dst_id] = Op::LShift(lhs_id, rhs.as_str().unsigned());
+            } else if let Some(lhs) = caps.name("lhs_rshift")
+                && let Some(rhs) = caps.name("rhs_rshift")
+            {
+                let dst_id = wire_to_id(&mut state, &caps["dst_wire"]);
+                let lhs_id = wire_to_id(&mut state, lhs.as_str());
+                state[dst_id] = Op::RShift(lhs_id, rhs.as_str().unsigned());
+            } else if let Some(rhs) = caps.name("rhs_not") {
+                let dst_id = wire_to_id(&mut state, &caps["dst_wire"]);
+                let rhs_id = wire_to_id(&mut state, rhs.as_str());
+                state[dst_id] = Op::Not(rhs_id);
+            } else {
+                return Err(ParseError(format!("Bad input: {}", caps[0].to_owned())).into());
+            }
+        }
 
-    // This recalculates but idc tbh
-    state[id_map["b"]] = Op::Const(part1(&input.clone()).unwrap());
+        let wire: String = params.get("wire")?;
+        let Some(&wire) = id_map.get(&wire) else {
+            return Err(ParseError(format!("No wire named {wire}")).into());
+        };
+        let override_wire = id_map.get(&params.get::<String>("override")?).copied();
+
+        Ok(Circuit {
+            state,
+            wire,
+            override_wire,
+        })
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Option<u64> {
+        calc(&mut input.state.clone(), input.wire).into()
+    }
+
+    fn part2(input: &Self::Input<'_>, part1: Option<&u64>) -> Option<u64> {
+        let override_wire = input.override_wire?;
+
+        let a = match part1 {
+            Some(&a) => a,
+            None => Self::part1(input)?,
+        };
+
+        let mut state = input.state.clone();
+        state[override_wire] = Op::Const(a);
 
-    calc(&mut state, id_map["a"]).into()
+        calc(&mut state, input.wire).into()
+    }
+
+    /// A circuit of `size` wires, each fed only by wires before it so there are no loops. The
+    /// first is "b" and the last, which is measured, is "a".
+    fn generate(seed: u64, size: usize) -> Option<String> {
+        let mut rng = Rng::new(seed);
+        let size = size.max(2);
+
+        let mut names: Vec<String> = (0..size).map(wire_name).collect();
+        names[0] = "b".to_owned();
+        names[size - 1] = "a".to_owned();
+
+        let mut lines = vec![];
+        for (i, dst) in names.iter().enumerate() {
+            let wire = |rng: &mut Rng| names[rng.below(i as u64) as usize].as_str();
+            let op = match if i < 2 { 0 } else { rng.below(8) } {
+                0 => rng.below(1 << 16).to_string(),
+                1 => wire(&mut rng).to_owned(),
+                2 => format!("1 AND {}", wire(&mut rng)),
+                3 => format!("{} AND {}", wire(&mut rng), wire(&mut rng)),
+                4 => format!("{} OR {}", wire(&mut rng), wire(&mut rng)),
+                5 => format!("{} LSHIFT {}", wire(&mut rng), rng.range(1..16)),
+                6 => format!("{} RSHIFT {}", wire(&mut rng), rng.range(1..16)),
+                _ => format!("NOT {}", wire(&mut rng)),
+            };
+            lines.push(format!("{op} -> {dst}"));
+        }
+
+        // Real inputs aren't in dependency order either
+        rng.shuffle(&mut lines);
+
+        Some(lines.join("\n") + "\n")
+    }
 }
@@ -1,13 +1,7 @@
 use std::error::Error;
 
-pub type ParsedInput = Vec<String>;
-
-pub fn parse(input: &str) -> Result<ParsedInput, Box<dyn Error>> {
-    Ok(input
-        .lines()
-        .map(|l| l[1..l.len() - 1].to_owned())
-        .collect())
-}
+use crate::util::rng::Rng;
+use crate::{Day, Params};
 
 fn memory_len(s: &str) -> usize {
     let mut len = 0;
@@ -35,13 +29,6 @@ fn memory_len(s: &str) -> usize {
     len
 }
 
-pub fn part1(input: &ParsedInput) -> Option<i64> {
-    let total_code_len = input.iter().map(|l| l.len() + 2).sum::<usize>() as i64;
-    let total_memory_len = input.iter().map(|l| memory_len(l)).sum::<usize>() as i64;
-
-    Some(total_code_len - total_memory_len)
-}
-
 fn encoded_len(s: &str) -> usize {
     s.chars()
         .map(|c| match c {
@@ -53,9 +40,83 @@ fn encoded_len(s: &str) -> usize {
         + 6
 }
 
-pub fn part2(input: &ParsedInput) -> Option<i64> {
-    let total_code_len = input.iter().map(|l| l.len() + 2).sum::<usize>() as i64;
-    let total_encoded_len = input.iter().map(|l| encoded_len(l)).sum::<usize>() as i64;
+pub struct Day08;
+
+impl Day for Day08 {
+    type Input<'a> = Vec<String>;
+    type Part1 = i64;
+    type Part2 = i64;
 
-    Some(total_encoded_len - total_code_len)
+    fn parse<'a>(input: &'a str, _: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        Ok(input
+            .lines()
+            .map(|l| l[1..l.len() - 1].to_owned())
+            .collect())
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Option<i64> {
+        let total_code_len = input.iter().map(|l| l.len() + 2).sum::<usize>() as i64;
+        let total_memory_len = input.iter().map(|l| memory_len(l)).sum::<usize>() as i64;
+
+        Some(total_code_len - total_memory_len)
+    }
+
+    fn part2(input: &Self::Input<'_>, _: Option<&i64>) -> Option<i64> {
+        let total_code_len = input.iter().map(|l| l.len() + 2).sum::<usize>() as i64;
+        let total_encoded_len = input.iter().map(|l| encoded_len(l)).sum::<usize>() as i64;
+
+        Some(total_encoded_len - total_code_len)
+    }
+
+    /// `size` string literals mixing plain letters with each kind of escape.
+    fn generate(seed: u64, size: usize) -> Option<String> {
+        let mut rng = Rng::new(seed);
+
+        let lines: Vec<String> = (0..size.max(1))
+            .map(|_| {
+                let mut line = "\"".to_owned();
+                for _ in 0..rng.below(12) {
+                    match rng.below(4) {
+                        0 => line.push_str("\\\\"),
+                        1 => line.push_str("\\\""),
+                        2 => line += &format!("\\x{:02x}", rng.below(256)),
+                        _ => line.push(*rng.choose(b"abcxyz0189") as char),
+                    }
+                }
+                line.push('"');
+                line
+            })
+            .collect();
+
+        Some(lines.join("\n") + "\n")
+    }
+
+    fn reference(input: &str, _: &Params) -> Option<(Option<i64>, Option<i64>)> {
+        let (mut code, mut memory, mut encoded) = (0, 0, 0);
+
+        for line in input.lines() {
+            let mut decoded: Vec<u8> = vec![];
+            let mut bytes = line.strip_prefix('"')?.strip_suffix('"')?.bytes();
+            while let Some(b) = bytes.next() {
+                decoded.push(match b {
+                    b'\\' => match bytes.next()? {
+                        b'x' => {
+                            let hex = [bytes.next()?, bytes.next()?];
+                            u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?
+                        }
+                        escaped => escaped,
+                    },
+                    _ => b,
+                });
+            }
+
+            let reencoded = format!("\"{}\"", line.replace('\\', "\\\\").replace('"', "\\\""));
+
+            code += line.len() as i64;
+            memory += decoded.len() as i64;
+            encoded += reencoded.len() as i64;
+        }
+
+        Some((Some(code - memory), Some(encoded - code)))
+    }
 }
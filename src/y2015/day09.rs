@@ -4,34 +4,10 @@ use std::error::Error;
 use crate::util::array::CastExt;
 use crate::util::id_cache::IdCache;
 use crate::util::parse::ParseOps;
+use crate::util::rng::Rng;
+use crate::{Day, Params};
 
-pub type ParsedInput = Vec<Vec<u64>>;
-
-pub fn parse(input: &str) -> Result<ParsedInput, Box<dyn Error>> {
-    let lines: Vec<[&str; 5]> = input
-        .lines()
-        .map(|l| l.split_whitespace().cast().unwrap())
-        .collect();
-
-    let mut ids = IdCache::default();
-    for line in &lines {
-        ids.get_or_add(line[0].to_owned());
-        ids.get_or_add(line[2].to_owned());
-    }
-
-    let mut dm = vec![vec![0u64; ids.len()]; ids.len()];
-    for line in &lines {
-        let from = ids.get(line[0]);
-        let to = ids.get(line[2]);
-        let dist: u64 = line[4].unsigned();
-        dm[from][to] = dist;
-        dm[to][from] = dist;
-    }
-
-    Ok(dm)
-}
-
-fn opti_hamilton(ord: Ordering, dm: &ParsedInput, from: usize, visited: &mut [bool]) -> u64 {
+fn opti_hamilton(ord: Ordering, dm: &[Vec<u64>], from: usize, visited: &mut [bool]) -> u64 {
     let sentinel = if ord == Ordering::Less {
         u64::MAX
     } else {
@@ -55,16 +31,65 @@ fn opti_hamilton(ord: Ordering, dm: &ParsedInput, from: usize, visited: &mut [bo
     if opti_len == sentinel { 0 } else { opti_len }
 }
 
-pub fn part1(input: &ParsedInput) -> Option<u64> {
-    let mut visited = vec![false; input.len()];
-    (0..input.len())
-        .map(|start| opti_hamilton(Ordering::Less, input, start, &mut visited))
-        .min()
-}
+pub struct Day09;
+
+impl Day for Day09 {
+    type Input<'a> = Vec<Vec<u64>>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse<'a>(input: &'a str, _: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        let lines: Vec<[&str; 5]> = input
+            .lines()
+            .map(|l| l.split_whitespace().cast().unwrap())
+            .collect();
+
+        let mut ids = IdCache::default();
+        for line in &lines {
+            ids.get_or_add(line[0].to_owned());
+            ids.get_or_add(line[2].to_owned());
+        }
 
-pub fn part2(input: &ParsedInput) -> Option<u64> {
-    let mut visited = vec![false; input.len()];
-    (0..input.len())
-        .map(|start| opti_hamilton(Ordering::Greater, input, start, &mut visited))
-        .max()
+        let mut dm = vec![vec![0u64; ids.len()]; ids.len()];
+        for line in &lines {
+            let from = ids.get(line[0]);
+            let to = ids.get(line[2]);
+            let dist: u64 = line[4].unsigned();
+            dm[from][to] = dist;
+            dm[to][from] = dist;
+        }
+
+        Ok(dm)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Option<u64> {
+        let mut visited = vec![false; input.len()];
+        (0..input.len())
+            .map(|start| opti_hamilton(Ordering::Less, input, start, &mut visited))
+            .min()
+    }
+
+    fn part2(input: &Self::Input<'_>, _: Option<&u64>) -> Option<u64> {
+        let mut visited = vec![false; input.len()];
+        (0..input.len())
+            .map(|start| opti_hamilton(Ordering::Greater, input, start, &mut visited))
+            .max()
+    }
+
+    /// Distances between every pair of `size` cities.
+    fn generate(seed: u64, size: usize) -> Option<String> {
+        let mut rng = Rng::new(seed);
+        // Fewer than two cities have no distances to list
+        let size = size.max(2);
+        let mut lines = vec![];
+
+        for from in 0..size {
+            for to in from + 1..size {
+                let dist = rng.range(1..200);
+                lines.push(format!("City{from} to City{to} = {dist}"));
+            }
+        }
+
+        Some(lines.join("\n") + "\n")
+    }
 }
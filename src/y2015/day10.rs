@@ -3,7 +3,7 @@ use std::iter::once;
 
 use itertools::Itertools;
 
-pub type ParsedInput = (usize, usize);
+use crate::{Answer, Day, Param, Params};
 
 fn say(seq: &str) -> String {
     let mut s = String::new();
@@ -25,21 +25,65 @@ fn say(seq: &str) -> String {
     s
 }
 
-pub fn parse(input: &str) -> Result<ParsedInput, Box<dyn Error>> {
-    let mut seq = input.trim().to_string();
+pub struct Rounds<'a> {
+    start: &'a str,
+    iterations: usize,
+    extra_iterations: usize,
+}
+
+/// The sequence after part 1's rounds. It answers with its length, and part 2 carries on from it.
+pub struct Sequence(String);
+
+impl From<Sequence> for Answer {
+    fn from(seq: Sequence) -> Self { seq.0.len().into() }
+}
 
-    for _ in 0..40 {
+fn say_times(seq: &str, rounds: usize) -> String {
+    let mut seq = seq.to_owned();
+    for _ in 0..rounds {
         seq = say(&seq);
     }
-    let p1_ans = seq.len();
+    seq
+}
 
-    for _ in 0..10 {
-        seq = say(&seq);
+pub struct Day10;
+
+impl Day for Day10 {
+    type Input<'a> = Rounds<'a>;
+    type Part1 = Sequence;
+    type Part2 = usize;
+
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "iterations",
+            default: "40",
+            help: "Rounds of look-and-say for part 1",
+        },
+        Param {
+            name: "extra_iterations",
+            default: "10",
+            help: "Further rounds for part 2",
+        },
+    ];
+
+    fn parse<'a>(input: &'a str, params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        Ok(Rounds {
+            start: input.trim(),
+            iterations: params.get("iterations")?,
+            extra_iterations: params.get("extra_iterations")?,
+        })
     }
 
-    Ok((p1_ans, seq.len()))
-}
+    fn part1(input: &Self::Input<'_>) -> Option<Sequence> {
+        Sequence(say_times(input.start, input.iterations)).into()
+    }
 
-pub fn part1(input: &ParsedInput) -> Option<usize> { input.0.into() }
+    fn part2(input: &Self::Input<'_>, part1: Option<&Sequence>) -> Option<usize> {
+        let seq = match part1 {
+            Some(Sequence(seq)) => say_times(seq, input.extra_iterations),
+            None => say_times(input.start, input.iterations + input.extra_iterations),
+        };
 
-pub fn part2(input: &ParsedInput) -> Option<usize> { input.1.into() }
+        seq.len().into()
+    }
+}
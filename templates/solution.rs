@@ -0,0 +1,36 @@
+//! {{year}} Day {{day}}: {{title}}
+
+use std::error::Error;
+
+use crate::{Day, Params};
+
+pub struct Day{{day:02}};
+
+impl Day for Day{{day:02}} {
+    type Input<'a> = &'a str;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse<'a>(input: &'a str, _: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        Ok(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = {{example}};
+
+    #[test]
+    fn part1_example() {
+        let input = Day{{day:02}}::parse(EXAMPLE, &Params::defaults(Day{{day:02}}::PARAMS)).unwrap();
+        assert_eq!(Day{{day:02}}::part1(&input), None);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day{{day:02}}::parse(EXAMPLE, &Params::defaults(Day{{day:02}}::PARAMS)).unwrap();
+        assert_eq!(Day{{day:02}}::part2(&input, None), None);
+    }
+}
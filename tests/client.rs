@@ -0,0 +1,223 @@
+#![cfg(feature = "cli")]
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use std::{env, process};
+
+use aoc::cache::HttpCache;
+use aoc::client::{AocClient, Hint, SessionExpiredError, SubmitOutcome};
+use aoc::{Answer, Part};
+
+/// Wraps a message the way the answer page does.
+fn page(message: &str) -> String {
+    format!("<html><body><main><article><p>{message}</p></article></main></body></html>")
+}
+
+const CORRECT: &str = "That's the right answer!  You are <span class=\"day-success\">one gold \
+                       star</span> closer to powering the weather machine.";
+const TOO_LOW: &str = "That's not the right answer; your answer is too low.  If you're stuck, \
+                       make sure you're using the full input data. Please wait one minute before \
+                       trying again. [<a href=\"/2015/day/1\">Return to Day 1</a>]";
+const TOO_HIGH: &str = "That's not the right answer; your answer is too high.  Please wait one \
+                        minute before trying again. [<a href=\"/2015/day/1\">Return to Day 1</a>]";
+const NO_HINT: &str = "That's not the right answer.  If you're stuck, make sure you're using the \
+                       full input data. [<a href=\"/2015/day/1\">Return to Day 1</a>]";
+const TOO_RECENT: &str = "You gave an answer too recently; you have to wait after submitting an \
+                          answer before trying again.  You have 4m 51s left to wait. \
+                          [<a href=\"/2015/day/1\">Return to Day 1</a>]";
+const WRONG_LEVEL: &str = "You don't seem to be solving the right level.  Did you already \
+                           complete it? [<a href=\"/2015/day/1\">Return to Day 1</a>]";
+
+#[test]
+fn parse_correct() {
+    assert_eq!(
+        SubmitOutcome::parse(&page(CORRECT)).unwrap(),
+        SubmitOutcome::Correct
+    );
+}
+
+#[test]
+fn parse_incorrect() {
+    assert_eq!(
+        SubmitOutcome::parse(&page(TOO_LOW)).unwrap(),
+        SubmitOutcome::Incorrect(Some(Hint::TooLow))
+    );
+    assert_eq!(
+        SubmitOutcome::parse(&page(TOO_HIGH)).unwrap(),
+        SubmitOutcome::Incorrect(Some(Hint::TooHigh))
+    );
+    assert_eq!(
+        SubmitOutcome::parse(&page(NO_HINT)).unwrap(),
+        SubmitOutcome::Incorrect(None)
+    );
+}
+
+#[test]
+fn parse_too_recent() {
+    assert_eq!(
+        SubmitOutcome::parse(&page(TOO_RECENT)).unwrap(),
+        SubmitOutcome::TooRecent(Some(Duration::from_secs(4 * 60 + 51)))
+    );
+
+    let seconds_only = TOO_RECENT.replace("4m 51s", "34s");
+    assert_eq!(
+        SubmitOutcome::parse(&page(&seconds_only)).unwrap(),
+        SubmitOutcome::TooRecent(Some(Duration::from_secs(34)))
+    );
+}
+
+#[test]
+fn parse_wrong_level() {
+    assert_eq!(
+        SubmitOutcome::parse(&page(WRONG_LEVEL)).unwrap(),
+        SubmitOutcome::WrongLevel
+    );
+}
+
+#[test]
+fn parse_unrecognised() {
+    assert!(SubmitOutcome::parse(&page("Something else entirely")).is_err());
+    assert!(SubmitOutcome::parse("<html><body>No article here</body></html>").is_err());
+}
+
+/// A request as the stand-in server saw it.
+struct Request {
+    head: String,
+    body: String,
+}
+
+/// Serves one request on a local port, answering with `status` and `extra_headers` plus `body`.
+/// Returns the base URL to point the client at and a handle yielding the request received.
+fn serve_once(status: &str, extra_headers: &str, body: String) -> (String, JoinHandle<Request>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let status = status.to_owned();
+    let extra_headers = extra_headers.to_owned();
+
+    let handle = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+
+        let mut head = String::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+            head += &line;
+        }
+
+        let length = head
+            .lines()
+            .find_map(|line| {
+                line.to_lowercase()
+                    .strip_prefix("content-length:")
+                    .map(str::to_owned)
+            })
+            .map_or(0, |length| length.trim().parse().unwrap());
+        let mut request_body = vec![0; length];
+        reader.read_exact(&mut request_body).unwrap();
+
+        let mut stream = reader.into_inner();
+        write!(
+            stream,
+            "HTTP/1.1 {status}\r\n{extra_headers}Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+
+        Request {
+            head,
+            body: String::from_utf8(request_body).unwrap(),
+        }
+    });
+
+    (base_url, handle)
+}
+
+/// A cache of this test run's own, so entries left by earlier runs can't be served.
+fn cache() -> HttpCache {
+    HttpCache::new(env::temp_dir().join(format!("aoc-client-tests-{}", process::id())))
+}
+
+fn client(base_url: &str) -> AocClient { AocClient::new(base_url, "test-token", cache()).unwrap() }
+
+#[test]
+fn submit_posts_the_answer_form() {
+    let (base_url, server) = serve_once("200 OK", "", page(TOO_LOW));
+
+    let outcome = client(&base_url)
+        .submit(2015, 1, Part::Two, &Answer::Integer(42))
+        .unwrap();
+    assert_eq!(outcome, SubmitOutcome::Incorrect(Some(Hint::TooLow)));
+
+    let request = server.join().unwrap();
+    assert!(
+        request
+            .head
+            .starts_with("POST /2015/day/1/answer HTTP/1.1\r\n")
+    );
+    assert!(request.head.contains("cookie: session=test-token\r\n"));
+    assert!(
+        request
+            .head
+            .contains("content-type: application/x-www-form-urlencoded\r\n")
+    );
+    assert_eq!(request.body, "level=2&answer=42");
+}
+
+#[test]
+fn submit_reports_an_expired_session() {
+    let (base_url, server) = serve_once("302 Found", "Location: /auth/login\r\n", String::new());
+
+    let err = client(&base_url)
+        .submit(2015, 1, Part::One, &Answer::Integer(42))
+        .unwrap_err();
+    assert!(err.is::<SessionExpiredError>());
+
+    server.join().unwrap();
+}
+
+#[test]
+fn submit_refuses_invalid_answers_without_contacting_the_server() {
+    // Nothing listens on a port that was bound and released, so a request would fail to connect
+    let base_url = {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    };
+
+    let err = client(&base_url)
+        .submit(2015, 1, Part::One, &Answer::Text("two words".to_owned()))
+        .unwrap_err();
+    assert!(err.to_string().starts_with("Refusing to submit answer"));
+}
+
+#[test]
+fn puzzle_pages_are_cached() {
+    let article = "<html><body><main><article class=\"day-desc\"><h2>--- Day 4: The Ideal \
+                   Stocking Stuffer ---</h2><p>Santa needs help mining.</p></article></main>\
+                   </body></html>";
+    let (base_url, server) = serve_once("200 OK", "", article.to_owned());
+
+    let client = client(&base_url);
+    assert_eq!(client.puzzle_page(2015, 4, false).unwrap(), article);
+    server.join().unwrap();
+
+    // Served from the cache now that nothing is listening any more
+    assert_eq!(client.puzzle_page(2015, 4, false).unwrap(), article);
+}
+
+#[test]
+fn puzzle_pages_without_a_description_are_not_cached() {
+    let (base_url, server) = serve_once("200 OK", "", page("Please log in"));
+
+    let client = client(&base_url);
+    client.puzzle_page(2015, 4, false).unwrap();
+    server.join().unwrap();
+
+    let url = client.puzzle_url(2015, 4).unwrap();
+    assert_eq!(cache().get(url.as_str()), None);
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "y2015")]
+
+use aoc::{Mismatch, Params, find_solution};
+
+const SIZE: usize = 20;
+const SEEDS: std::ops::Range<u64> = 0..100;
+
+/// Asserts that the solution agrees with its reference solution on every generated input.
+fn assert_matches_reference(year: u32, day: u32) {
+    let solution = find_solution(year, day).unwrap();
+
+    for Mismatch {
+        seed,
+        expected,
+        actual,
+    } in solution.cross_check(SIZE, SEEDS).unwrap()
+    {
+        assert_eq!(
+            actual,
+            Ok(expected),
+            "{year} Day {day:02} disagreed with the reference on seed {seed}"
+        );
+    }
+}
+
+#[test]
+fn y2015_day05_matches_reference() { assert_matches_reference(2015, 5); }
+
+#[test]
+fn y2015_day08_matches_reference() { assert_matches_reference(2015, 8); }
+
+#[test]
+fn y2015_day08_reference_rejects_unquoted_lines() {
+    let solution = find_solution(2015, 8).unwrap();
+    let params = Params::defaults(solution.solver.params());
+
+    for input in ["\"\n", "\n", "abc\n", "\"abc\n"] {
+        assert_eq!(solution.solver.reference(input, &params), None);
+    }
+}
+
+#[test]
+fn y2015_generators_make_valid_inputs_at_any_size() {
+    for day in [6, 7, 8, 9] {
+        let solution = find_solution(2015, day).unwrap();
+        let params = Params::defaults(solution.solver.params());
+
+        for size in 0..3 {
+            let input = solution.solver.generate(0, size).unwrap();
+            assert!(
+                solution.solve(&input, &params).is_ok(),
+                "2015 Day {day:02} failed on an input of size {size}:\n{input}"
+            );
+        }
+    }
+}
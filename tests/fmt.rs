@@ -0,0 +1,47 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The `src/yYYYY/dayDD.rs` files, which are only reached through the `include!` in `lib.rs`.
+fn day_files(src_dir: &Path) -> Vec<PathBuf> {
+    let mut files = vec![];
+    for year_dir in fs::read_dir(src_dir).unwrap() {
+        let year_dir = year_dir.unwrap().path();
+        let is_year = year_dir
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with('y') && year_dir.is_dir());
+        if !is_year {
+            continue;
+        }
+
+        for day in fs::read_dir(&year_dir).unwrap() {
+            let day = day.unwrap().path();
+            if day.extension().is_some_and(|ext| ext == "rs") {
+                files.push(day);
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+/// `cargo fmt` can't follow the generated module tree into the day files, so check them here.
+#[test]
+fn day_files_are_formatted() {
+    let src_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    let files = day_files(&src_dir);
+    assert!(!files.is_empty());
+
+    let output = Command::new("rustfmt")
+        .args(["--edition", "2024", "--check"])
+        .args(&files)
+        .output()
+        .expect("rustfmt should be installed");
+
+    assert!(
+        output.status.success(),
+        "Day files need formatting, run `rustfmt --edition 2024 src/y*/day*.rs`:\n{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}